@@ -0,0 +1,23 @@
+//! Compares `A8Mini::poll_attitude` against `get_attitude_information` over loopback, against the
+//! mock camera. Round trips dominate, so the difference is the per-call encoding and parsing.
+//!
+//! Run with `cargo bench --bench attitude_polling --features mock`.
+
+use a8mini_camera_rs::test_support::MockCamera;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn attitude_polling(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let mock = runtime.block_on(MockCamera::spawn()).unwrap();
+    let camera = runtime.block_on(mock.connect()).unwrap();
+
+    c.bench_function("get_attitude_information", |b| {
+        b.to_async(&runtime).iter(|| async { camera.get_attitude_information().await.unwrap() })
+    });
+    c.bench_function("poll_attitude", |b| {
+        b.to_async(&runtime).iter(|| async { camera.poll_attitude().await.unwrap() })
+    });
+}
+
+criterion_group!(benches, attitude_polling);
+criterion_main!(benches);
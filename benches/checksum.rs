@@ -0,0 +1,25 @@
+//! Baseline for CRC16 and frame building, to catch regressions as commands are added.
+//!
+//! Run with `cargo bench --bench checksum`.
+
+use a8mini_camera_rs::checksum::crc16_calc;
+use a8mini_camera_rs::constants::MAX_FRAME_LEN;
+use a8mini_camera_rs::control::{build_frame, write_frame};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+fn checksum(c: &mut Criterion) {
+    // header and payload of a SetYawPitchAngle frame, the CRC's input
+    let command = [0x55, 0x66, 0x01, 0x04, 0x00, 0x00, 0x00, 0x0e, 0xc2, 0x01, 0xd4, 0xfe, 0x00];
+    let payload = [0xc2, 0x01, 0xd4, 0xfe];
+    let mut buf = [0u8; MAX_FRAME_LEN];
+
+    c.bench_function("crc16 (13 bytes)", |b| b.iter(|| crc16_calc(black_box(&command), 0)));
+    c.bench_function("build_frame", |b| b.iter(|| build_frame(black_box(0x0e), black_box(&payload))));
+    c.bench_function("write_frame", |b| {
+        b.iter(|| write_frame(&mut buf, black_box(0x0e), black_box(&payload)).unwrap())
+    });
+}
+
+criterion_group!(benches, checksum);
+criterion_main!(benches);
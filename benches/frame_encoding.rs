@@ -0,0 +1,22 @@
+//! Compares serializing command frames into a reused buffer against allocating a `Vec` each time.
+//!
+//! Run with `cargo bench --bench frame_encoding`.
+
+use a8mini_camera_rs::control::{A8MiniComplexCommand, A8MiniSimpleCommand, Command};
+use a8mini_camera_rs::constants::MAX_FRAME_LEN;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+fn frame_encoding(c: &mut Criterion) {
+    let simple = A8MiniSimpleCommand::AttitudeInformation;
+    let complex = A8MiniComplexCommand::SetYawPitchAngle(450, -300);
+    let mut buf = [0u8; MAX_FRAME_LEN];
+
+    c.bench_function("simple to_bytes", |b| b.iter(|| black_box(simple).to_bytes()));
+    c.bench_function("simple write_to", |b| b.iter(|| black_box(simple).write_to(&mut buf).unwrap()));
+    c.bench_function("complex to_bytes", |b| b.iter(|| black_box(complex).to_bytes()));
+    c.bench_function("complex write_to", |b| b.iter(|| black_box(complex).write_to(&mut buf).unwrap()));
+}
+
+criterion_group!(benches, frame_encoding);
+criterion_main!(benches);
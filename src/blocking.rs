@@ -0,0 +1,151 @@
+//! Synchronous wrapper around `A8Mini`, for callers without a tokio runtime.
+//!
+//! Each call runs to completion on a runtime owned by the `A8MiniBlocking`, like reqwest's
+//! blocking client. Don't use it from within an async context: blocking on a runtime from inside
+//! another one panics.
+
+use crate::{control, A8Mini, A8MiniBuilder, DownloadSummary};
+use std::io::Write;
+use std::path::Path;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::AsyncWrite;
+use tokio::runtime::Runtime;
+
+/// Blocking handle to the camera. Mirrors the matching `A8Mini` methods.
+#[derive(Debug)]
+pub struct A8MiniBlocking {
+    inner: A8Mini,
+    runtime: Runtime,
+}
+
+impl A8MiniBlocking {
+    /// Connects using the camera's factory settings. See `A8Mini::connect`.
+    pub fn connect() -> anyhow::Result<Self> {
+        Self::connect_with(A8Mini::builder())
+    }
+
+    /// Connects with the settings of `builder`.
+    pub fn connect_with(builder: A8MiniBuilder) -> anyhow::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+        let inner = runtime.block_on(builder.build())?;
+        Ok(A8MiniBlocking { inner, runtime })
+    }
+
+    /// The wrapped async client. Its methods must not be awaited outside `A8MiniBlocking`'s runtime.
+    pub fn inner(&self) -> &A8Mini {
+        &self.inner
+    }
+
+    pub fn send_command_blind<T: control::Command>(&self, command: T) -> anyhow::Result<()> {
+        self.runtime.block_on(self.inner.send_command_blind(command))
+    }
+
+    pub fn send_command<T: control::Command>(&self, command: T) -> anyhow::Result<control::AckFrame> {
+        self.runtime.block_on(self.inner.send_command(command))
+    }
+
+    pub fn get_attitude_information(&self) -> anyhow::Result<control::A8MiniAttitude> {
+        self.runtime.block_on(self.inner.get_attitude_information())
+    }
+
+    pub fn get_photo_information(&self) -> anyhow::Result<i32> {
+        self.runtime.block_on(self.inner.get_photo_information())
+    }
+
+    pub fn get_video_count(&self) -> anyhow::Result<i32> {
+        self.runtime.block_on(self.inner.get_video_count())
+    }
+
+    pub fn media_count(&self, kind: control::MediaKind) -> anyhow::Result<i32> {
+        self.runtime.block_on(self.inner.media_count(kind))
+    }
+
+    pub fn take_photo_and_download(&self) -> anyhow::Result<Vec<u8>> {
+        self.runtime.block_on(self.inner.take_photo_and_download())
+    }
+
+    pub fn send_http_query<T: control::HTTPQuery>(&self, query: T) -> anyhow::Result<control::HTTPResponse<T::Response>> {
+        self.runtime.block_on(self.inner.send_http_query(query))
+    }
+
+    pub fn send_http_media_query<T: control::HTTPQuery>(&self, query: T) -> anyhow::Result<Vec<u8>> {
+        self.runtime.block_on(self.inner.send_http_media_query(query))
+    }
+
+    pub fn download_all(
+        &self,
+        kind: control::MediaKind,
+        dest_dir: &Path,
+        concurrency: usize,
+    ) -> anyhow::Result<DownloadSummary> {
+        self.runtime.block_on(self.inner.download_all(kind, dest_dir, concurrency))
+    }
+
+    /// Streams a file from the camera into `writer`. Returns the number of bytes written.
+    pub fn download_media_to<T: control::HTTPQuery, W: Write + Unpin>(
+        &self,
+        query: T,
+        writer: &mut W,
+    ) -> anyhow::Result<u64> {
+        self.runtime
+            .block_on(self.inner.download_media_to(query, &mut SyncWriter(writer)))
+    }
+}
+
+/// Lets `A8Mini`'s async download methods write into a `std::io::Write`. Writes block the
+/// runtime, which is fine since it only ever drives the one call.
+struct SyncWriter<'a, W>(&'a mut W);
+
+impl<W: Write + Unpin> AsyncWrite for SyncWriter<'_, W> {
+    fn poll_write(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        Poll::Ready(self.get_mut().0.write(buf))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(self.get_mut().0.flush())
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{fake_jpeg, MockCamera};
+
+    /// Runs the mock on its own runtime, as a blocking caller's peer would be.
+    fn spawn_mock() -> (Runtime, MockCamera) {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .unwrap();
+        let mock = runtime.block_on(MockCamera::spawn()).unwrap();
+        (runtime, mock)
+    }
+
+    fn connect(mock: &MockCamera) -> A8MiniBlocking {
+        A8MiniBlocking::connect_with(mock.builder()).unwrap()
+    }
+
+    #[test]
+    fn test_blocking_attitude_and_media() {
+        let (_runtime, mock) = spawn_mock();
+        mock.state().attitude.theta_yaw = 123;
+        let camera = connect(&mock);
+
+        assert_eq!(camera.get_attitude_information().unwrap().theta_yaw, 123);
+        assert_eq!(camera.take_photo_and_download().unwrap(), fake_jpeg(1));
+        assert_eq!(camera.get_photo_information().unwrap(), 1);
+
+        let mut file = Vec::new();
+        let written = camera
+            .download_media_to(control::A8MiniComplexHTTPQuery::GetMedia(control::MediaKind::Photo, 1), &mut file)
+            .unwrap();
+        assert_eq!(written, file.len() as u64);
+        assert_eq!(file, fake_jpeg(1));
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{constants, A8Mini};
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr};
+use std::time::Duration;
+
+/// Addresses and timeout an `A8Mini` was connected with, as returned by `A8Mini::config`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectionConfig {
+    pub camera_ip: String,
+    pub command_port: String,
+    pub http_port: String,
+    pub local_command_port: String,
+    pub local_http_port: String,
+    /// Longest to wait for a command's ACK.
+    pub recv_timeout: Duration,
+}
+
+/// Configures and connects an `A8Mini`. Every setting defaults to the camera's factory values,
+/// so only what differs needs to be set:
+/// `A8Mini::builder().camera_ip("192.168.144.26").build().await`
+#[derive(Debug, Clone)]
+pub struct A8MiniBuilder {
+    pub(crate) camera_ip: String,
+    pub(crate) camera_command_port: String,
+    pub(crate) camera_http_port: String,
+    pub(crate) local_bind_ip: IpAddr,
+    pub(crate) local_command_port: String,
+    pub(crate) local_http_port: String,
+    pub(crate) rtsp_port: String,
+    pub(crate) rtsp_main_path: String,
+    pub(crate) rtsp_sub_path: String,
+    pub(crate) media_api_path: String,
+    pub(crate) verify_on_connect: bool,
+    pub(crate) http_attempts: u32,
+    pub(crate) http_retry_delay: Duration,
+    pub(crate) recv_buffer_size: usize,
+    pub(crate) connect_timeout: Duration,
+    pub(crate) recv_timeout: Duration,
+    pub(crate) crc_init: u16,
+    pub(crate) min_command_interval: Option<Duration>,
+    pub(crate) normalize_mounting: bool,
+    pub(crate) require_limits: bool,
+}
+
+impl Default for A8MiniBuilder {
+    fn default() -> Self {
+        A8MiniBuilder {
+            camera_ip: constants::CAMERA_IP.to_string(),
+            camera_command_port: constants::CAMERA_COMMAND_PORT.to_string(),
+            camera_http_port: constants::CAMERA_HTTP_PORT.to_string(),
+            local_bind_ip: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            local_command_port: constants::LOCAL_COMMAND_PORT.to_string(),
+            local_http_port: constants::LOCAL_HTTP_PORT.to_string(),
+            rtsp_port: constants::CAMERA_RTSP_PORT.to_string(),
+            rtsp_main_path: constants::RTSP_MAIN_PATH.to_string(),
+            rtsp_sub_path: constants::RTSP_SUB_PATH.to_string(),
+            media_api_path: constants::MEDIA_API_PATH.to_string(),
+            verify_on_connect: false,
+            http_attempts: constants::HTTP_ATTEMPTS,
+            http_retry_delay: constants::HTTP_RETRY_BASE_DELAY,
+            recv_buffer_size: constants::RECV_BUFF_SIZE,
+            connect_timeout: constants::CONNECT_TIMEOUT,
+            recv_timeout: constants::RECV_TIMEOUT,
+            crc_init: constants::CRC16_INIT,
+            min_command_interval: None,
+            normalize_mounting: false,
+            require_limits: false,
+        }
+    }
+}
+
+impl A8MiniBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn camera_ip(mut self, camera_ip: impl Into<String>) -> Self {
+        self.camera_ip = camera_ip.into();
+        self
+    }
+
+    pub fn camera_command_port(mut self, port: impl Into<String>) -> Self {
+        self.camera_command_port = port.into();
+        self
+    }
+
+    pub fn camera_http_port(mut self, port: impl Into<String>) -> Self {
+        self.camera_http_port = port.into();
+        self
+    }
+
+    /// Local interface both sockets, and every HTTP request, bind to. Defaults to `0.0.0.0`; set
+    /// it to the camera-facing NIC's address on multi-interface companion computers.
+    pub fn local_bind_ip(mut self, ip: IpAddr) -> Self {
+        self.local_bind_ip = ip;
+        self
+    }
+
+    /// Local port of the command socket. `"0"` picks an ephemeral port.
+    pub fn local_command_port(mut self, port: impl Into<String>) -> Self {
+        self.local_command_port = port.into();
+        self
+    }
+
+    /// Local port of the HTTP socket. `"0"` picks an ephemeral port.
+    pub fn local_http_port(mut self, port: impl Into<String>) -> Self {
+        self.local_http_port = port.into();
+        self
+    }
+
+    pub fn rtsp_port(mut self, port: impl Into<String>) -> Self {
+        self.rtsp_port = port.into();
+        self
+    }
+
+    /// Path of the main stream, including the leading `/`.
+    pub fn rtsp_main_path(mut self, path: impl Into<String>) -> Self {
+        self.rtsp_main_path = path.into();
+        self
+    }
+
+    /// Path of the sub stream, including the leading `/`.
+    pub fn rtsp_sub_path(mut self, path: impl Into<String>) -> Self {
+        self.rtsp_sub_path = path.into();
+        self
+    }
+
+    /// Prefix the HTTP media API is served under, including the leading `/`. Defaults to
+    /// `/cgi-bin/media.cgi`; some firmware revisions use another.
+    pub fn media_api_path(mut self, path: impl Into<String>) -> Self {
+        self.media_api_path = path.into();
+        self
+    }
+
+    /// Queries the firmware version during `build()`, failing with `A8MiniError::NoCameraFound`
+    /// if the camera doesn't answer. Off by default.
+    pub fn verify_on_connect(mut self, verify: bool) -> Self {
+        self.verify_on_connect = verify;
+        self
+    }
+
+    /// Makes `send_http_query` try up to `attempts` times in total when the camera's web server
+    /// resets the connection, times out or answers with a 5xx, as it does while booting. The
+    /// first retry waits `base_delay`, each later one twice as long as the one before. A 4xx is
+    /// never retried. Defaults to 3 attempts from 200 ms; 1 attempt turns retries off.
+    pub fn http_retry(mut self, attempts: u32, base_delay: Duration) -> Self {
+        self.http_attempts = attempts.max(1);
+        self.http_retry_delay = base_delay;
+        self
+    }
+
+    /// Longest `build()` may take to bind and connect the sockets, including the
+    /// `verify_on_connect` handshake, before failing with `A8MiniError::Timeout`, so a boot
+    /// script can't hang while the network stack comes up. Defaults to 5 s.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Longest to wait for a command's ACK. Defaults to 500 ms.
+    pub fn recv_timeout(mut self, timeout: Duration) -> Self {
+        self.recv_timeout = timeout;
+        self
+    }
+
+    /// Makes `send_command_blind` wait until at least `interval` has passed since the previous
+    /// blind send, so a fast control loop can't flood the gimbal's command buffer. Off by default.
+    pub fn min_command_interval(mut self, interval: Duration) -> Self {
+        self.min_command_interval = Some(interval);
+        self
+    }
+
+    /// Reads the mounting direction during `build()` and, if the gimbal is upside down, reports
+    /// attitudes as seen from an upright mount (see `A8MiniAttitude::inverted`). Off by default.
+    pub fn normalize_mounting(mut self, normalize: bool) -> Self {
+        self.normalize_mounting = normalize;
+        self
+    }
+
+    /// Refuses commands that move the gimbal until its model, and so its limits, is known,
+    /// querying the hardware ID first if needed. If the model can't be determined they fail
+    /// with `A8MiniError::ModelUnknown`. Off by default.
+    pub fn require_limits(mut self, require: bool) -> Self {
+        self.require_limits = require;
+        self
+    }
+
+    /// CRC16 initial value used for sent and received frames. Only needed for firmware that
+    /// rejects frames, or sends ACKs failing with `A8MiniError::CrcMismatch`, under the
+    /// documented value of 0.
+    pub fn crc_init(mut self, crc_init: u16) -> Self {
+        self.crc_init = crc_init;
+        self
+    }
+
+    /// Size of the buffer ACKs are received into. Longer datagrams are truncated and then fail
+    /// to parse, so raise it for firmware with longer responses. Defaults to 64 bytes.
+    pub fn recv_buffer_size(mut self, size: usize) -> Self {
+        self.recv_buffer_size = size;
+        self
+    }
+
+    /// The settings `config` describes, on top of the defaults for everything else.
+    pub fn from_config(config: &ConnectionConfig) -> Self {
+        Self::new()
+            .camera_ip(config.camera_ip.as_str())
+            .camera_command_port(config.command_port.as_str())
+            .camera_http_port(config.http_port.as_str())
+            .local_command_port(config.local_command_port.as_str())
+            .local_http_port(config.local_http_port.as_str())
+            .recv_timeout(config.recv_timeout)
+    }
+
+    /// Binds the local sockets and connects them to the camera.
+    pub async fn build(self) -> anyhow::Result<A8Mini> {
+        A8Mini::connect_with(self).await
+    }
+}
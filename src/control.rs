@@ -1,19 +1,84 @@
-use crate::{checksum, constants};
-use serde::{Deserialize, Serialize};
+use crate::{checksum, constants, error::A8MiniError};
+use chrono::{DateTime, Utc};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-/// Trait for camera commands
-pub trait Command {
-    fn to_bytes(&self) -> Vec<u8>;
+pub mod bytes;
+
+/// Trait for camera commands. `Debug` names the command in trace output. Implement at least one
+/// of `write_to` and `to_bytes`; each defaults to the other.
+pub trait Command: fmt::Debug {
+    /// Serializes the frame into `buf`, returning its length. Fails with
+    /// `A8MiniError::BufferTooSmall` if it doesn't fit; `constants::MAX_FRAME_LEN` always does
+    /// for this crate's commands. The other serializers are built on this.
+    fn write_to(&self, buf: &mut [u8]) -> Result<usize, A8MiniError>;
+
+    /// Same as `write_to`, into a new `Vec`. Panics if `write_to` fails for another reason than
+    /// the frame's length; see `try_to_bytes`.
+    fn to_bytes(&self) -> Vec<u8> {
+        self.try_to_bytes().expect("command serializes")
+    }
+
+    /// Same as `to_bytes`, returning `write_to`'s errors. Frames longer than
+    /// `constants::MAX_FRAME_LEN` get a buffer of the length `write_to` asks for.
+    fn try_to_bytes(&self) -> Result<Vec<u8>, A8MiniError> {
+        let mut buf = vec![0u8; constants::MAX_FRAME_LEN];
+        let len = match self.write_to(&mut buf) {
+            Err(A8MiniError::BufferTooSmall { needed, .. }) => {
+                buf.resize(needed, 0);
+                self.write_to(&mut buf)?
+            }
+            result => result?,
+        };
+        buf.truncate(len);
+        Ok(buf)
+    }
+
+    /// Whether `model` implements this command. Defaults to every model.
+    fn supported_by(&self, _model: &CameraModel) -> bool {
+        true
+    }
+
+    /// Whether this command turns the gimbal. Stopping doesn't count. Defaults to `false`.
+    fn moves_gimbal(&self) -> bool {
+        false
+    }
 }
 
-/// Trait for HTTP API queries
-pub trait HTTPQuery {
-    fn to_string(&self) -> String;
+/// Trait for HTTP API queries. `Debug` names the query in trace output.
+pub trait HTTPQuery: fmt::Debug {
+    /// `data` of the JSON answer, as returned by `A8Mini::send_http_query`.
+    type Response: DeserializeOwned;
+
+    /// Path and query string of the request, relative to the camera's HTTP root.
+    fn path(&self) -> String;
+
+    /// Same as `path`, with the media API served under `api_path` instead of
+    /// `constants::MEDIA_API_PATH`, as on some firmware revisions. Other paths are unchanged.
+    fn path_with_api_path(&self, api_path: &str) -> String {
+        let path = self.path();
+        match path.strip_prefix(constants::MEDIA_API_PATH) {
+            Some(endpoint) => format!("{}{}", api_path, endpoint),
+            None => path,
+        }
+    }
+
+    /// Full request URL against the default camera address and media API path.
+    fn url(&self) -> String {
+        format!(
+            "http://{}:{}{}",
+            constants::CAMERA_IP,
+            constants::CAMERA_HTTP_PORT,
+            self.path()
+        )
+    }
 }
 
 /// Enums for hardcoded simple commands.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum A8MiniSimpleCommand {
     AutoCenter = 0,   // handled ACK (sta)
     RotateUp = 1,     // handled ACK (sta)
@@ -23,6 +88,7 @@ pub enum A8MiniSimpleCommand {
     StopRotation = 5, // handled ACK (sta)
     ZoomIn = 6,       // handled ACK (sta)
     ZoomOut = 7,      // handled ACK (sta)
+    #[deprecated(note = "use `A8MiniComplexCommand::SetAbsoluteZoom(4, 5)`")]
     ZoomMax = 8,
     MaxZoomInformation = 9,
     FocusIn = 10,
@@ -47,61 +113,531 @@ pub enum A8MiniSimpleCommand {
     Resolution4k = 29,
     Heartbeat = 30,
     GimbalStatus = 31,
+    /// Pitches straight down (nadir), yaw centered, in any motion mode. `0x08` center position 4,
+    /// from the extended centering of the SIYI gimbal camera SDK (ZT30, ZR30, ZT6, ZR10 and
+    /// A8 mini on current firmware); older firmware only knows one-key centering and ignores it.
+    PointDown = 32,
+    /// Points straight ahead, like `AutoCenter`. `0x08` center position 3; see `PointDown`.
+    PointForward = 33,
 }
 
+// every variant indexes a frame, and the table has no frames without a variant
+const _: () = assert!(A8MiniSimpleCommand::PointForward as usize + 1 == constants::NUM_COMMANDS);
+
+#[allow(deprecated)]
 impl Command for A8MiniSimpleCommand {
-    fn to_bytes(&self) -> Vec<u8> {
-        constants::HARDCODED_COMMANDS[*self as usize].to_vec()
+    fn write_to(&self, buf: &mut [u8]) -> Result<usize, A8MiniError> {
+        if *self == A8MiniSimpleCommand::ZoomMax {
+            return A8MiniComplexCommand::SetAbsoluteZoom(4, 5).write_to(buf);
+        }
+        let frame = constants::HARDCODED_COMMANDS[*self as usize];
+        let actual = buf.len();
+        buf.get_mut(..frame.len())
+            .ok_or(A8MiniError::BufferTooSmall { needed: frame.len(), actual })?
+            .copy_from_slice(frame);
+        Ok(frame.len())
+    }
+
+    /// Video output switching only exists on the A8 mini and the laser rangefinder only on the
+    /// ZT30. Unknown models are given the benefit of the doubt.
+    fn supported_by(&self, model: &CameraModel) -> bool {
+        match self {
+            A8MiniSimpleCommand::SetVideoOutputHDMI
+            | A8MiniSimpleCommand::SetVideoOutputCVBS
+            | A8MiniSimpleCommand::SetVideoOutputOff => {
+                matches!(model, CameraModel::A8Mini | CameraModel::Unknown(_))
+            }
+            A8MiniSimpleCommand::LaserRangefinderInformation => {
+                matches!(model, CameraModel::Zt30 | CameraModel::Unknown(_))
+            }
+            _ => true,
+        }
+    }
+
+    fn moves_gimbal(&self) -> bool {
+        matches!(
+            self,
+            A8MiniSimpleCommand::AutoCenter
+                | A8MiniSimpleCommand::RotateUp
+                | A8MiniSimpleCommand::RotateDown
+                | A8MiniSimpleCommand::RotateRight
+                | A8MiniSimpleCommand::RotateLeft
+                | A8MiniSimpleCommand::Rotate100100
+                | A8MiniSimpleCommand::PointDown
+                | A8MiniSimpleCommand::PointForward
+        )
+    }
+}
+
+#[allow(deprecated)]
+impl A8MiniSimpleCommand {
+    /// Every simple command, in `HARDCODED_COMMANDS` order.
+    pub const ALL: [A8MiniSimpleCommand; constants::NUM_COMMANDS] = [
+        A8MiniSimpleCommand::AutoCenter,
+        A8MiniSimpleCommand::RotateUp,
+        A8MiniSimpleCommand::RotateDown,
+        A8MiniSimpleCommand::RotateRight,
+        A8MiniSimpleCommand::RotateLeft,
+        A8MiniSimpleCommand::StopRotation,
+        A8MiniSimpleCommand::ZoomIn,
+        A8MiniSimpleCommand::ZoomOut,
+        A8MiniSimpleCommand::ZoomMax,
+        A8MiniSimpleCommand::MaxZoomInformation,
+        A8MiniSimpleCommand::FocusIn,
+        A8MiniSimpleCommand::FocusOut,
+        A8MiniSimpleCommand::TakePicture,
+        A8MiniSimpleCommand::RecordVideo,
+        A8MiniSimpleCommand::Rotate100100,
+        A8MiniSimpleCommand::CameraInformation,
+        A8MiniSimpleCommand::AutoFocus,
+        A8MiniSimpleCommand::HardwareIDInformation,
+        A8MiniSimpleCommand::FirmwareVersionInformation,
+        A8MiniSimpleCommand::SetLockMode,
+        A8MiniSimpleCommand::SetFollowMode,
+        A8MiniSimpleCommand::SetFPVMode,
+        A8MiniSimpleCommand::AttitudeInformation,
+        A8MiniSimpleCommand::SetVideoOutputHDMI,
+        A8MiniSimpleCommand::SetVideoOutputCVBS,
+        A8MiniSimpleCommand::SetVideoOutputOff,
+        A8MiniSimpleCommand::LaserRangefinderInformation,
+        A8MiniSimpleCommand::RebootCamera,
+        A8MiniSimpleCommand::RebootGimbal,
+        A8MiniSimpleCommand::Resolution4k,
+        A8MiniSimpleCommand::Heartbeat,
+        A8MiniSimpleCommand::GimbalStatus,
+        A8MiniSimpleCommand::PointDown,
+        A8MiniSimpleCommand::PointForward,
+    ];
+
+    /// Every simple command, in `HARDCODED_COMMANDS` order.
+    pub fn all() -> &'static [A8MiniSimpleCommand] {
+        &Self::ALL
+    }
+
+    /// Variant name, as printed by `Display` and parsed by `FromStr`.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            A8MiniSimpleCommand::AutoCenter => "AutoCenter",
+            A8MiniSimpleCommand::RotateUp => "RotateUp",
+            A8MiniSimpleCommand::RotateDown => "RotateDown",
+            A8MiniSimpleCommand::RotateRight => "RotateRight",
+            A8MiniSimpleCommand::RotateLeft => "RotateLeft",
+            A8MiniSimpleCommand::StopRotation => "StopRotation",
+            A8MiniSimpleCommand::ZoomIn => "ZoomIn",
+            A8MiniSimpleCommand::ZoomOut => "ZoomOut",
+            A8MiniSimpleCommand::ZoomMax => "ZoomMax",
+            A8MiniSimpleCommand::MaxZoomInformation => "MaxZoomInformation",
+            A8MiniSimpleCommand::FocusIn => "FocusIn",
+            A8MiniSimpleCommand::FocusOut => "FocusOut",
+            A8MiniSimpleCommand::TakePicture => "TakePicture",
+            A8MiniSimpleCommand::RecordVideo => "RecordVideo",
+            A8MiniSimpleCommand::Rotate100100 => "Rotate100100",
+            A8MiniSimpleCommand::CameraInformation => "CameraInformation",
+            A8MiniSimpleCommand::AutoFocus => "AutoFocus",
+            A8MiniSimpleCommand::HardwareIDInformation => "HardwareIDInformation",
+            A8MiniSimpleCommand::FirmwareVersionInformation => "FirmwareVersionInformation",
+            A8MiniSimpleCommand::SetLockMode => "SetLockMode",
+            A8MiniSimpleCommand::SetFollowMode => "SetFollowMode",
+            A8MiniSimpleCommand::SetFPVMode => "SetFPVMode",
+            A8MiniSimpleCommand::AttitudeInformation => "AttitudeInformation",
+            A8MiniSimpleCommand::SetVideoOutputHDMI => "SetVideoOutputHDMI",
+            A8MiniSimpleCommand::SetVideoOutputCVBS => "SetVideoOutputCVBS",
+            A8MiniSimpleCommand::SetVideoOutputOff => "SetVideoOutputOff",
+            A8MiniSimpleCommand::LaserRangefinderInformation => "LaserRangefinderInformation",
+            A8MiniSimpleCommand::RebootCamera => "RebootCamera",
+            A8MiniSimpleCommand::RebootGimbal => "RebootGimbal",
+            A8MiniSimpleCommand::Resolution4k => "Resolution4k",
+            A8MiniSimpleCommand::Heartbeat => "Heartbeat",
+            A8MiniSimpleCommand::GimbalStatus => "GimbalStatus",
+            A8MiniSimpleCommand::PointDown => "PointDown",
+            A8MiniSimpleCommand::PointForward => "PointForward",
+        }
+    }
+}
+
+/// Prints the variant name, which `FromStr` parses back.
+impl fmt::Display for A8MiniSimpleCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for A8MiniSimpleCommand {
+    type Err = A8MiniError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        A8MiniSimpleCommand::ALL
+            .into_iter()
+            .find(|command| command.name() == s)
+            .ok_or_else(|| A8MiniError::UnknownCommand(s.to_string()))
+    }
+}
+
+/// Builds a complete request frame (STX, CTRL = need_ack, DATALEN, SEQ = 0, CMD_ID, DATA, CRC16).
+pub fn build_frame(command_id: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = vec![0; AckFrame::HEADER_LEN + payload.len() + AckFrame::CRC_LEN];
+    write_frame(&mut frame, command_id, payload).expect("frame is sized to fit");
+    frame
+}
+
+/// Same as `build_frame`, into `buf`. Returns the frame length.
+pub fn write_frame(buf: &mut [u8], command_id: u8, payload: &[u8]) -> Result<usize, A8MiniError> {
+    let crc_start = AckFrame::HEADER_LEN + payload.len();
+    let frame_len = crc_start + AckFrame::CRC_LEN;
+    let actual = buf.len();
+    let frame = buf
+        .get_mut(..frame_len)
+        .ok_or(A8MiniError::BufferTooSmall { needed: frame_len, actual })?;
+
+    frame[..3].copy_from_slice(&[0x55, 0x66, 0x01]);
+    frame[3..5].copy_from_slice(&(payload.len() as u16).to_le_bytes());
+    frame[5..7].copy_from_slice(&[0x00, 0x00]);
+    frame[7] = command_id;
+    frame[AckFrame::HEADER_LEN..crc_start].copy_from_slice(payload);
+
+    let crc = checksum::crc16_calc(&frame[..crc_start], constants::CRC16_INIT);
+    frame[crc_start..].copy_from_slice(&crc);
+
+    Ok(frame_len)
+}
+
+/// Either kind of command, so commands can be stored together, e.g. in `A8Mini::run_sequence` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum A8MiniCommand {
+    Simple(A8MiniSimpleCommand),
+    Complex(A8MiniComplexCommand),
+}
+
+impl Command for A8MiniCommand {
+    fn write_to(&self, buf: &mut [u8]) -> Result<usize, A8MiniError> {
+        match self {
+            A8MiniCommand::Simple(command) => command.write_to(buf),
+            A8MiniCommand::Complex(command) => command.write_to(buf),
+        }
+    }
+
+    fn supported_by(&self, model: &CameraModel) -> bool {
+        match self {
+            A8MiniCommand::Simple(command) => command.supported_by(model),
+            A8MiniCommand::Complex(command) => command.supported_by(model),
+        }
+    }
+
+    fn moves_gimbal(&self) -> bool {
+        match self {
+            A8MiniCommand::Simple(command) => command.moves_gimbal(),
+            A8MiniCommand::Complex(command) => command.moves_gimbal(),
+        }
+    }
+}
+
+impl From<A8MiniSimpleCommand> for A8MiniCommand {
+    fn from(command: A8MiniSimpleCommand) -> Self {
+        A8MiniCommand::Simple(command)
+    }
+}
+
+impl From<A8MiniComplexCommand> for A8MiniCommand {
+    fn from(command: A8MiniComplexCommand) -> Self {
+        A8MiniCommand::Complex(command)
     }
 }
 
+/// Rewrites the SEQ field of a frame built by `Command::to_bytes` and recomputes its CRC.
+/// Fails with `A8MiniError::FrameTooShort` if `frame` can't hold a header and CRC.
+pub fn stamp_sequence(frame: &mut [u8], sequence: u16) -> Result<(), A8MiniError> {
+    let min_len = AckFrame::HEADER_LEN + AckFrame::CRC_LEN;
+    if frame.len() < min_len {
+        return Err(A8MiniError::FrameTooShort { expected: min_len, actual: frame.len() });
+    }
+    frame[5..7].copy_from_slice(&sequence.to_le_bytes());
+    stamp_crc(frame, constants::CRC16_INIT);
+    Ok(())
+}
+
+/// Recomputes the CRC trailer of a complete frame, starting from `crc_init`.
+pub fn stamp_crc(frame: &mut [u8], crc_init: u16) {
+    let crc_start = frame.len() - AckFrame::CRC_LEN;
+    let crc = checksum::crc16_calc(&frame[..crc_start], crc_init);
+    frame[crc_start..].copy_from_slice(&crc);
+}
+
 /// Enums for commands that require continuous values for data field.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum A8MiniComplexCommand {
     SetYawPitchSpeed(i8, i8),
     SetYawPitchAngle(i16, i16),
-    SetTimeUTC(u64),
-    GetCodecSpecs(u8),                        // TODO: WIP
-    SetCodecSpecs(u8, u8, u16, u16, u16, u8), // TODO: WIP
+    SetTimeUTC(SystemTime),
+    GetCodecSpecs(u8),                        // stream type: 0 recording, 1 main, 2 sub
+    SetCodecSpecs(u8, u8, u16, u16, u16, u8), // stream, codec, width, height, bitrate (kbps), reserved
     RequestGimbalDataStream(u8, u8),          // gimbal data stream
+    SetAbsoluteZoom(u8, u8),                  // integer part, tenths
+    GetZoomLevel,
 }
 
-impl Command for A8MiniComplexCommand {
-    fn to_bytes(&self) -> Vec<u8> {
-        match *self {
-            A8MiniComplexCommand::SetYawPitchSpeed(v_yaw, v_pitch) => {
-                let mut byte_arr: Vec<u8> = vec![0x55, 0x66, 0x01, 0x02, 0x00, 0x00, 0x00, 0x07];
+impl A8MiniComplexCommand {
+    /// Builds a `SetYawPitchAngle` from degrees, rejecting angles outside the A8 mini's
+    /// mechanical limits instead of letting the gimbal silently ignore them.
+    pub fn yaw_pitch_angle_deg(yaw_deg: f32, pitch_deg: f32) -> Result<Self, A8MiniError> {
+        Self::yaw_pitch_angle_deg_within(yaw_deg, pitch_deg, &GimbalLimits::A8_MINI)
+    }
 
-                byte_arr.push(v_yaw.clamp(-100, 100) as u8);
-                byte_arr.push(v_pitch.clamp(-100, 100) as u8);
+    /// Same as `yaw_pitch_angle_deg`, checking against `limits.commandable()` of another gimbal.
+    pub fn yaw_pitch_angle_deg_within(yaw_deg: f32, pitch_deg: f32, limits: &GimbalLimits) -> Result<Self, A8MiniError> {
+        limits.commandable().check(yaw_deg, pitch_deg)?;
 
-                byte_arr.extend_from_slice(&checksum::crc16_calc(&byte_arr, 0));
+        Ok(A8MiniComplexCommand::SetYawPitchAngle(
+            (yaw_deg * 10.0).round() as i16,
+            (pitch_deg * 10.0).round() as i16,
+        ))
+    }
 
-                byte_arr
+    /// Builds a `SetYawPitchSpeed` from joystick axes in `[-1.0, 1.0]`, clamping values outside
+    /// it. Deflections within `deadband` of center map to 0; beyond it the remaining travel is
+    /// scaled to the full speed range, so the speed doesn't jump at the deadband's edge.
+    pub fn yaw_pitch_speed_normalized(yaw: f32, pitch: f32, deadband: f32) -> Self {
+        let deadband = deadband.clamp(0.0, 1.0);
+        let speed = |axis: f32| {
+            let axis = if axis.is_nan() { 0.0 } else { axis.clamp(-1.0, 1.0) };
+            if axis.abs() <= deadband {
+                return 0;
             }
-            A8MiniComplexCommand::SetYawPitchAngle(theta_yaw, theta_pitch) => {
-                let mut byte_arr: Vec<u8> = vec![0x55, 0x66, 0x01, 0x04, 0x00, 0x00, 0x00, 0x0e];
+            let scaled = (axis.abs() - deadband) / (1.0 - deadband);
+            (axis.signum() * scaled * 100.0).round() as i8
+        };
+        A8MiniComplexCommand::SetYawPitchSpeed(speed(yaw), speed(pitch))
+    }
+
+    /// Builds a `SetAbsoluteZoom` to `factor`, rounded to a tenth. Fails with
+    /// `A8MiniError::OutOfRange` outside `1.0..=max_zoom`.
+    pub fn absolute_zoom(factor: f32, max_zoom: f32) -> Result<Self, A8MiniError> {
+        check_range("zoom", factor, 1.0, max_zoom)?;
+
+        let tenths = (factor * 10.0).round() as u16;
+        Ok(A8MiniComplexCommand::SetAbsoluteZoom((tenths / 10) as u8, (tenths % 10) as u8))
+    }
+}
+
+/// Decodes a zoom factor from the integer and tenths bytes of a `0x16` or `0x18` ACK payload.
+pub fn zoom_from_payload(payload: &[u8]) -> Result<f32, A8MiniError> {
+    match payload {
+        [integer, tenths, ..] => Ok(*integer as f32 + *tenths as f32 / 10.0),
+        _ => Err(A8MiniError::FrameTooShort { expected: 2, actual: payload.len() }),
+    }
+}
+
+/// Angles a gimbal can be pointed at, in degrees.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GimbalLimits {
+    pub yaw_range: RangeInclusive<f32>,
+    pub pitch_range: RangeInclusive<f32>,
+    pub roll_range: RangeInclusive<f32>,
+}
+
+impl GimbalLimits {
+    pub const A8_MINI: GimbalLimits = GimbalLimits {
+        yaw_range: -135.0..=135.0,
+        pitch_range: -90.0..=25.0,
+        roll_range: -45.0..=45.0,
+    };
+    /// Single-axis gimbal: pitch only.
+    pub const A2_MINI: GimbalLimits = GimbalLimits {
+        yaw_range: 0.0..=0.0,
+        pitch_range: -90.0..=25.0,
+        roll_range: 0.0..=0.0,
+    };
+    /// Also the ZT6 and ZR30. Their yaw turns further than `SetYawPitchAngle` goes, which is
+    /// capped at ±135° for every model.
+    pub const ZT30: GimbalLimits = GimbalLimits {
+        yaw_range: -270.0..=270.0,
+        pitch_range: -90.0..=25.0,
+        roll_range: -45.0..=45.0,
+    };
 
-                byte_arr.extend_from_slice(&theta_yaw.clamp(-1350, 1350).to_le_bytes());
-                byte_arr.extend_from_slice(&theta_pitch.clamp(-900, 250).to_le_bytes());
+    /// Limits of `model`. Unknown models get the A8 mini's, the narrowest three-axis range.
+    pub fn for_model(model: &CameraModel) -> Self {
+        match model {
+            CameraModel::A2Mini => Self::A2_MINI,
+            CameraModel::Zr30 | CameraModel::Zt6 | CameraModel::Zt30 => Self::ZT30,
+            CameraModel::A8Mini | CameraModel::Zr10 | CameraModel::Unknown(_) => Self::A8_MINI,
+        }
+    }
+
+    /// These limits with yaw narrowed to the ±135° `SetYawPitchAngle` can carry.
+    pub fn commandable(&self) -> GimbalLimits {
+        let yaw_range = self.yaw_range.start().max(-135.0)..=self.yaw_range.end().min(135.0);
+        GimbalLimits { yaw_range, ..self.clone() }
+    }
+
+    /// The nearest `(yaw_deg, pitch_deg)` the gimbal can point at.
+    pub fn clamp(&self, yaw_deg: f32, pitch_deg: f32) -> (f32, f32) {
+        (
+            yaw_deg.clamp(*self.yaw_range.start(), *self.yaw_range.end()),
+            pitch_deg.clamp(*self.pitch_range.start(), *self.pitch_range.end()),
+        )
+    }
+
+    /// Fails with `A8MiniError::OutOfRange` if the gimbal can't point at `yaw_deg`, `pitch_deg`.
+    pub fn check(&self, yaw_deg: f32, pitch_deg: f32) -> Result<(), A8MiniError> {
+        check_range("yaw_deg", yaw_deg, *self.yaw_range.start(), *self.yaw_range.end())?;
+        check_range("pitch_deg", pitch_deg, *self.pitch_range.start(), *self.pitch_range.end())
+    }
+}
 
-                byte_arr.extend_from_slice(&checksum::crc16_calc(&byte_arr, 0));
+fn check_range(name: &'static str, value: f32, min: f32, max: f32) -> Result<(), A8MiniError> {
+    // also rejects NaN
+    if !(min..=max).contains(&value) {
+        return Err(A8MiniError::OutOfRange { name, value, min, max });
+    }
+    Ok(())
+}
 
-                byte_arr
+/// Prints the variant name followed by its space-separated arguments, e.g.
+/// `SetYawPitchSpeed 50 -10`, which `FromStr` parses back. `SetTimeUTC` takes seconds since the
+/// UNIX epoch with an optional fraction of up to 6 digits.
+impl fmt::Display for A8MiniComplexCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            A8MiniComplexCommand::SetYawPitchSpeed(yaw, pitch) => write!(f, "SetYawPitchSpeed {} {}", yaw, pitch),
+            A8MiniComplexCommand::SetYawPitchAngle(yaw, pitch) => write!(f, "SetYawPitchAngle {} {}", yaw, pitch),
+            A8MiniComplexCommand::SetTimeUTC(time) => {
+                let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+                write!(f, "SetTimeUTC {}.{:06}", since_epoch.as_secs(), since_epoch.subsec_micros())
+            }
+            A8MiniComplexCommand::GetCodecSpecs(stream_type) => write!(f, "GetCodecSpecs {}", stream_type),
+            A8MiniComplexCommand::SetCodecSpecs(stream_type, codec, width, height, bitrate, reserved) => write!(
+                f,
+                "SetCodecSpecs {} {} {} {} {} {}",
+                stream_type, codec, width, height, bitrate, reserved
+            ),
+            A8MiniComplexCommand::RequestGimbalDataStream(data_type, data_freq) => {
+                write!(f, "RequestGimbalDataStream {} {}", data_type, data_freq)
+            }
+            A8MiniComplexCommand::SetAbsoluteZoom(integer, tenths) => {
+                write!(f, "SetAbsoluteZoom {} {}", integer, tenths)
             }
-            A8MiniComplexCommand::SetTimeUTC(timestamp) => {
-                let mut byte_arr: Vec<u8> = vec![0x55, 0x66, 0x01, 0x04, 0x00, 0x00, 0x00, 0x30];
+            A8MiniComplexCommand::GetZoomLevel => write!(f, "GetZoomLevel"),
+        }
+    }
+}
+
+impl FromStr for A8MiniComplexCommand {
+    type Err = A8MiniError;
 
-                byte_arr.extend_from_slice(&timestamp.to_le_bytes());
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = s.split_whitespace();
+        let name = tokens.next().unwrap_or_default();
+        let tokens: Vec<&str> = tokens.collect();
 
-                byte_arr
+        let args = |command: &'static str, expected: &'static str, count: usize| {
+            if tokens.len() == count {
+                Ok(CommandArgs { command, expected, tokens: &tokens })
+            } else {
+                Err(A8MiniError::InvalidArguments { command, expected })
             }
-            A8MiniComplexCommand::GetCodecSpecs(stream_type) => {
-                let mut byte_arr: Vec<u8> = vec![0x55, 0x66, 0x01, 0x04, 0x00, 0x00, 0x00, 0x20];
+        };
+
+        match name {
+            "SetYawPitchSpeed" => {
+                let args = args("SetYawPitchSpeed", "<yaw i8> <pitch i8>", 2)?;
+                Ok(A8MiniComplexCommand::SetYawPitchSpeed(args.get(0)?, args.get(1)?))
+            }
+            "SetYawPitchAngle" => {
+                let args = args("SetYawPitchAngle", "<yaw i16> <pitch i16>", 2)?;
+                Ok(A8MiniComplexCommand::SetYawPitchAngle(args.get(0)?, args.get(1)?))
+            }
+            "SetTimeUTC" => {
+                let args = args("SetTimeUTC", "<secs>[.<micros>]", 1)?;
+                let (secs, fraction) = args.tokens[0].split_once('.').unwrap_or((args.tokens[0], ""));
+                if fraction.len() > 6 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(args.invalid());
+                }
+                let secs: u64 = secs.parse().map_err(|_| args.invalid())?;
+                // right-pad so ".5" reads as 500000 µs
+                let micros: u64 = format!("{:0<6}", fraction).parse().map_err(|_| args.invalid())?;
+                Ok(A8MiniComplexCommand::SetTimeUTC(
+                    UNIX_EPOCH + Duration::from_secs(secs) + Duration::from_micros(micros),
+                ))
+            }
+            "GetCodecSpecs" => {
+                let args = args("GetCodecSpecs", "<stream u8>", 1)?;
+                Ok(A8MiniComplexCommand::GetCodecSpecs(args.get(0)?))
+            }
+            "SetCodecSpecs" => {
+                let args = args(
+                    "SetCodecSpecs",
+                    "<stream u8> <codec u8> <width u16> <height u16> <bitrate u16> <reserved u8>",
+                    6,
+                )?;
+                Ok(A8MiniComplexCommand::SetCodecSpecs(
+                    args.get(0)?,
+                    args.get(1)?,
+                    args.get(2)?,
+                    args.get(3)?,
+                    args.get(4)?,
+                    args.get(5)?,
+                ))
+            }
+            "RequestGimbalDataStream" => {
+                let args = args("RequestGimbalDataStream", "<data_type u8> <data_freq u8>", 2)?;
+                Ok(A8MiniComplexCommand::RequestGimbalDataStream(args.get(0)?, args.get(1)?))
+            }
+            "SetAbsoluteZoom" => {
+                let args = args("SetAbsoluteZoom", "<integer u8> <tenths u8>", 2)?;
+                Ok(A8MiniComplexCommand::SetAbsoluteZoom(args.get(0)?, args.get(1)?))
+            }
+            "GetZoomLevel" => {
+                args("GetZoomLevel", "no arguments", 0)?;
+                Ok(A8MiniComplexCommand::GetZoomLevel)
+            }
+            _ => Err(A8MiniError::UnknownCommand(name.to_string())),
+        }
+    }
+}
+
+/// Arguments of a complex command being parsed, already checked to have the right count.
+struct CommandArgs<'a> {
+    command: &'static str,
+    expected: &'static str,
+    tokens: &'a [&'a str],
+}
+
+impl CommandArgs<'_> {
+    fn invalid(&self) -> A8MiniError {
+        A8MiniError::InvalidArguments { command: self.command, expected: self.expected }
+    }
+
+    fn get<T: FromStr>(&self, index: usize) -> Result<T, A8MiniError> {
+        self.tokens[index].parse().map_err(|_| self.invalid())
+    }
+}
+
+impl Command for A8MiniComplexCommand {
+    fn write_to(&self, buf: &mut [u8]) -> Result<usize, A8MiniError> {
+        match *self {
+            A8MiniComplexCommand::SetYawPitchSpeed(v_yaw, v_pitch) => write_frame(
+                buf,
+                0x07,
+                &[v_yaw.clamp(-100, 100) as u8, v_pitch.clamp(-100, 100) as u8],
+            ),
+            A8MiniComplexCommand::SetYawPitchAngle(theta_yaw, theta_pitch) => {
+                let mut payload = [0u8; 4];
+                payload[..2].copy_from_slice(&theta_yaw.clamp(-1350, 1350).to_le_bytes());
+                payload[2..].copy_from_slice(&theta_pitch.clamp(-900, 250).to_le_bytes());
 
-                byte_arr.extend_from_slice(&stream_type.clamp(0, 2).to_le_bytes());
+                write_frame(buf, 0x0E, &payload)
+            }
+            A8MiniComplexCommand::SetTimeUTC(time) => {
+                // microseconds since the UNIX epoch; times before it are sent as 0
+                let micros = time
+                    .duration_since(UNIX_EPOCH)
+                    .map(|since_epoch| since_epoch.as_micros() as u64)
+                    .unwrap_or(0);
 
-                byte_arr
+                write_frame(buf, 0x30, &micros.to_le_bytes())
+            }
+            A8MiniComplexCommand::GetCodecSpecs(stream_type) => {
+                write_frame(buf, 0x20, &[stream_type.clamp(0, 2)])
             }
             A8MiniComplexCommand::SetCodecSpecs(
                 stream_type,
@@ -109,94 +645,399 @@ impl Command for A8MiniComplexCommand {
                 resolution_l,
                 resolution_h,
                 video_bitrate,
-                _,
+                reserved,
             ) => {
-                let mut byte_arr: Vec<u8> = vec![0x55, 0x66, 0x01, 0x04, 0x00, 0x00, 0x00, 0x21];
-
-                byte_arr.extend_from_slice(&stream_type.clamp(0, 2).to_le_bytes());
-                byte_arr.extend_from_slice(&video_enc_type.clamp(1, 2).to_le_bytes());
+                let mut payload = [0u8; 9];
 
-                // TODO: make sure resolution_l and resolution_h are clamped to only 1920/1280 and 1080/720 respectively
-                byte_arr.extend_from_slice(&resolution_l.to_le_bytes());
-                byte_arr.extend_from_slice(&resolution_h.to_le_bytes());
+                payload[0] = stream_type.clamp(0, 2);
+                payload[1] = video_enc_type.clamp(1, 2);
 
-                // TODO: make sure video bitrate is reasonable
-                byte_arr.extend_from_slice(&video_bitrate.to_le_bytes());
+                // not validated here; see `CodecConfig` for the checked path
+                payload[2..4].copy_from_slice(&resolution_l.to_le_bytes());
+                payload[4..6].copy_from_slice(&resolution_h.to_le_bytes());
+                payload[6..8].copy_from_slice(&video_bitrate.to_le_bytes());
+                payload[8] = reserved;
 
-                byte_arr
+                write_frame(buf, 0x21, &payload)
             }
-            // implementation for 0x25 Request Gimbal Data Stream
+            // 0x25 Request Gimbal Data Stream
             A8MiniComplexCommand::RequestGimbalDataStream(data_type, data_freq) => {
-                // Header (STX + CTRL + DataLen=2 + Seq=0 + CmdID=0x25)
-                let mut byte_arr: Vec<u8> = vec![0x55, 0x66, 0x01, 0x02, 0x00, 0x00, 0x00, 0x25];
-                
-                byte_arr.push(data_type);
-                byte_arr.push(data_freq);
-                
-                byte_arr.extend_from_slice(&checksum::crc16_calc(&byte_arr, 0));
-                byte_arr
+                write_frame(buf, 0x25, &[data_type, data_freq])
             }
+            A8MiniComplexCommand::SetAbsoluteZoom(integer, tenths) => {
+                write_frame(buf, 0x0F, &[integer, tenths.clamp(0, 9)])
+            }
+            A8MiniComplexCommand::GetZoomLevel => write_frame(buf, 0x18, &[]),
+        }
+    }
+
+    fn moves_gimbal(&self) -> bool {
+        match *self {
+            A8MiniComplexCommand::SetYawPitchSpeed(v_yaw, v_pitch) => v_yaw != 0 || v_pitch != 0,
+            A8MiniComplexCommand::SetYawPitchAngle(..) => true,
+            _ => false,
         }
     }
 }
 
 /// Enums for simple HTTP queries
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum A8MiniSimpleHTTPQuery {
+    #[deprecated(note = "use `A8MiniComplexHTTPQuery::GetDirectories(MediaKind::Photo)`")]
     GetDirectoriesPhotos,
+    #[deprecated(note = "use `A8MiniComplexHTTPQuery::GetDirectories(MediaKind::Video)`")]
     GetDirectoriesVideos,
+    #[deprecated(note = "use `A8MiniComplexHTTPQuery::GetMediaCount(MediaKind::Photo)`")]
     GetMediaCountPhotos,
+    #[deprecated(note = "use `A8MiniComplexHTTPQuery::GetMediaCount(MediaKind::Video)`")]
     GetMediaCountVideos,
 }
 
+#[allow(deprecated)]
 impl HTTPQuery for A8MiniSimpleHTTPQuery {
-    fn to_string(&self) -> String {
+    type Response = HTTPResponseData;
+
+    fn path(&self) -> String {
         match *self {
-            A8MiniSimpleHTTPQuery::GetDirectoriesPhotos => "http://192.168.144.25:82/cgi-bin/media.cgi/api/v1/getdirectories?media_type=0".to_string(),
-            A8MiniSimpleHTTPQuery::GetDirectoriesVideos => "http://192.168.144.25:82/cgi-bin/media.cgi/api/v1/getdirectories?media_type=1".to_string(),
-            A8MiniSimpleHTTPQuery::GetMediaCountPhotos => "http://192.168.144.25:82/cgi-bin/media.cgi/api/v1/getmediacount?media_type=0&path=101SIYI_IMG".to_string(),
-            A8MiniSimpleHTTPQuery::GetMediaCountVideos => "http://192.168.144.25:82/cgi-bin/media.cgi/api/v1/getmediacount?media_type=1&path=100SIYI_VID".to_string(),
+            A8MiniSimpleHTTPQuery::GetDirectoriesPhotos => format!("{}/api/v1/getdirectories?media_type=0", constants::MEDIA_API_PATH),
+            A8MiniSimpleHTTPQuery::GetDirectoriesVideos => format!("{}/api/v1/getdirectories?media_type=1", constants::MEDIA_API_PATH),
+            A8MiniSimpleHTTPQuery::GetMediaCountPhotos => format!("{}/api/v1/getmediacount?media_type=0&path=101SIYI_IMG", constants::MEDIA_API_PATH),
+            A8MiniSimpleHTTPQuery::GetMediaCountVideos => format!("{}/api/v1/getmediacount?media_type=1&path=100SIYI_VID", constants::MEDIA_API_PATH),
         }
     }
 }
 
-/// Enums for complex HTTP queries
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Prints the equivalent `A8MiniComplexHTTPQuery`, e.g. `GetDirectories 0`.
+#[allow(deprecated)]
+impl fmt::Display for A8MiniSimpleHTTPQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let query = match *self {
+            A8MiniSimpleHTTPQuery::GetDirectoriesPhotos => A8MiniComplexHTTPQuery::GetDirectories(MediaKind::Photo),
+            A8MiniSimpleHTTPQuery::GetDirectoriesVideos => A8MiniComplexHTTPQuery::GetDirectories(MediaKind::Video),
+            A8MiniSimpleHTTPQuery::GetMediaCountPhotos => A8MiniComplexHTTPQuery::GetMediaCount(MediaKind::Photo),
+            A8MiniSimpleHTTPQuery::GetMediaCountVideos => A8MiniComplexHTTPQuery::GetMediaCount(MediaKind::Video),
+        };
+        fmt::Display::fmt(&query, f)
+    }
+}
+
+/// Kind of media stored on the camera's SD card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MediaKind {
+    Photo,
+    Video,
+}
+
+impl MediaKind {
+    /// Value of the `media_type` parameter of the HTTP API.
+    pub fn media_type(&self) -> u8 {
+        match *self {
+            MediaKind::Photo => 0,
+            MediaKind::Video => 1,
+        }
+    }
+
+    /// Kind for a `media_type` value, or `None` for unknown values.
+    pub fn from_media_type(media_type: u8) -> Option<Self> {
+        match media_type {
+            0 => Some(MediaKind::Photo),
+            1 => Some(MediaKind::Video),
+            _ => None,
+        }
+    }
+
+    /// Default SD card folder the camera stores this kind of media in.
+    pub fn directory(&self) -> &'static str {
+        match *self {
+            MediaKind::Photo => "101SIYI_IMG",
+            MediaKind::Video => "100SIYI_VID",
+        }
+    }
+
+    /// Name the camera gives the `index`-th file of this kind, e.g. `IMG_0001.jpg`.
+    pub fn file_name(&self, index: u32) -> String {
+        match *self {
+            MediaKind::Photo => format!("IMG_{:0>4}.jpg", index),
+            MediaKind::Video => format!("REC_{:0>4}.mp4", index),
+        }
+    }
+
+    /// Query returning how many files of this kind are on the SD card.
+    pub fn count_query(&self) -> A8MiniComplexHTTPQuery {
+        A8MiniComplexHTTPQuery::GetMediaCount(*self)
+    }
+
+    /// Query downloading the file of this kind called `name`.
+    pub fn file_query(&self, name: impl Into<String>) -> A8MiniComplexHTTPQuery {
+        match *self {
+            MediaKind::Photo => A8MiniComplexHTTPQuery::GetPhotoByName(name.into()),
+            MediaKind::Video => A8MiniComplexHTTPQuery::GetVideoByName(name.into()),
+        }
+    }
+}
+
+/// Enums for complex HTTP queries. Answered with the legacy `HTTPResponseData`; `DirectoriesQuery`,
+/// `MediaCountQuery` and `MediaListQuery` are the typed forms of the JSON endpoints.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum A8MiniComplexHTTPQuery {
+    GetDirectories(MediaKind),
+    GetMediaCount(MediaKind),
+    /// File by its index, as numbered by the camera.
+    GetMedia(MediaKind, u32),
+    #[deprecated(note = "use `A8MiniComplexHTTPQuery::GetMedia(MediaKind::Photo, index)`")]
     GetPhoto(u32),
+    #[deprecated(note = "use `A8MiniComplexHTTPQuery::GetMedia(MediaKind::Video, index)`")]
     GetVideo(u32),
+    GetMediaList { kind: MediaKind, start: u32, count: u32 },
+    GetPhotoByName(String),
+    GetVideoByName(String),
 }
 
+/// Answers with the catch-all `HTTPResponseData`, as one enum covers several endpoints. The
+/// typed `DirectoriesQuery`, `MediaCountQuery` and `MediaListQuery` decode exactly their fields.
+#[allow(deprecated)]
 impl HTTPQuery for A8MiniComplexHTTPQuery {
-    fn to_string(&self) -> String {
+    type Response = HTTPResponseData;
+
+    fn path(&self) -> String {
         match *self {
-            A8MiniComplexHTTPQuery::GetPhoto(photo_ind) => format!(
-                "http://192.168.144.25:82/photo/101SIYI_IMG/IMG_{:0>4}.jpg",
-                photo_ind
+            A8MiniComplexHTTPQuery::GetDirectories(kind) => format!(
+                "{}/api/v1/getdirectories?media_type={}",
+                constants::MEDIA_API_PATH,
+                kind.media_type()
+            ),
+            A8MiniComplexHTTPQuery::GetMediaCount(kind) => format!(
+                "{}/api/v1/getmediacount?media_type={}&path={}",
+                constants::MEDIA_API_PATH,
+                kind.media_type(),
+                kind.directory()
+            ),
+            A8MiniComplexHTTPQuery::GetMedia(kind, index) => {
+                format!("/photo/{}/{}", kind.directory(), kind.file_name(index))
+            }
+            A8MiniComplexHTTPQuery::GetPhoto(photo_ind) => {
+                A8MiniComplexHTTPQuery::GetMedia(MediaKind::Photo, photo_ind).path()
+            }
+            A8MiniComplexHTTPQuery::GetVideo(video_ind) => {
+                A8MiniComplexHTTPQuery::GetMedia(MediaKind::Video, video_ind).path()
+            }
+            A8MiniComplexHTTPQuery::GetMediaList { kind, start, count } => format!(
+                "{}/api/v1/getmedialist?media_type={}&path={}&start={}&count={}",
+                constants::MEDIA_API_PATH,
+                kind.media_type(),
+                kind.directory(),
+                start,
+                count
+            ),
+            A8MiniComplexHTTPQuery::GetPhotoByName(ref name) => format!(
+                "/photo/{}/{}",
+                MediaKind::Photo.directory(),
+                percent_encode(name)
             ),
-            A8MiniComplexHTTPQuery::GetVideo(video_ind) => format!(
-                "http://192.168.144.25:82/photo/100SIYI_VID/REC_{:0>4}.mp4",
-                video_ind
+            A8MiniComplexHTTPQuery::GetVideoByName(ref name) => format!(
+                "/photo/{}/{}",
+                MediaKind::Video.directory(),
+                percent_encode(name)
             ),
         }
     }
 }
 
-/// Response json format
+/// Prints the CLI form: the variant name followed by its space-separated arguments, with the
+/// media kind as its `media_type` (0 photo, 1 video), e.g. `GetMedia 1 3`. The deprecated
+/// `GetPhoto`/`GetVideo` print as the equivalent `GetMedia`.
+#[allow(deprecated)]
+impl fmt::Display for A8MiniComplexHTTPQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            A8MiniComplexHTTPQuery::GetDirectories(kind) => write!(f, "GetDirectories {}", kind.media_type()),
+            A8MiniComplexHTTPQuery::GetMediaCount(kind) => write!(f, "GetMediaCount {}", kind.media_type()),
+            A8MiniComplexHTTPQuery::GetMedia(kind, index) => write!(f, "GetMedia {} {}", kind.media_type(), index),
+            A8MiniComplexHTTPQuery::GetPhoto(index) => write!(f, "GetMedia 0 {}", index),
+            A8MiniComplexHTTPQuery::GetVideo(index) => write!(f, "GetMedia 1 {}", index),
+            A8MiniComplexHTTPQuery::GetMediaList { kind, start, count } => {
+                write!(f, "GetMediaList {} {} {}", kind.media_type(), start, count)
+            }
+            A8MiniComplexHTTPQuery::GetPhotoByName(ref name) => write!(f, "GetPhotoByName {}", name),
+            A8MiniComplexHTTPQuery::GetVideoByName(ref name) => write!(f, "GetVideoByName {}", name),
+        }
+    }
+}
+
+impl FromStr for A8MiniComplexHTTPQuery {
+    type Err = A8MiniError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = s.split_whitespace();
+        let name = tokens.next().unwrap_or_default();
+        let tokens: Vec<&str> = tokens.collect();
+
+        let args = |command: &'static str, expected: &'static str, count: usize| {
+            if tokens.len() == count {
+                Ok(CommandArgs { command, expected, tokens: &tokens })
+            } else {
+                Err(A8MiniError::InvalidArguments { command, expected })
+            }
+        };
+        let kind = |args: &CommandArgs| MediaKind::from_media_type(args.get(0)?).ok_or_else(|| args.invalid());
+        // names may contain spaces
+        let file_name = |command: &'static str| {
+            if tokens.is_empty() {
+                return Err(A8MiniError::InvalidArguments { command, expected: "<name>" });
+            }
+            Ok(tokens.join(" "))
+        };
+
+        match name {
+            "GetDirectories" => {
+                let args = args("GetDirectories", "<kind 0|1>", 1)?;
+                Ok(A8MiniComplexHTTPQuery::GetDirectories(kind(&args)?))
+            }
+            "GetMediaCount" => {
+                let args = args("GetMediaCount", "<kind 0|1>", 1)?;
+                Ok(A8MiniComplexHTTPQuery::GetMediaCount(kind(&args)?))
+            }
+            "GetMedia" => {
+                let args = args("GetMedia", "<kind 0|1> <index u32>", 2)?;
+                Ok(A8MiniComplexHTTPQuery::GetMedia(kind(&args)?, args.get(1)?))
+            }
+            "GetMediaList" => {
+                let args = args("GetMediaList", "<kind 0|1> <start u32> <count u32>", 3)?;
+                Ok(A8MiniComplexHTTPQuery::GetMediaList { kind: kind(&args)?, start: args.get(1)?, count: args.get(2)? })
+            }
+            "GetPhotoByName" => Ok(A8MiniComplexHTTPQuery::GetPhotoByName(file_name("GetPhotoByName")?)),
+            "GetVideoByName" => Ok(A8MiniComplexHTTPQuery::GetVideoByName(file_name("GetVideoByName")?)),
+            _ => Err(A8MiniError::UnknownCommand(name.to_string())),
+        }
+    }
+}
+
+/// Percent-encodes everything but RFC 3986 unreserved characters, for use in a URL path segment.
+fn percent_encode(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Lists the media directories of a kind. Typed form of `A8MiniComplexHTTPQuery::GetDirectories`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DirectoriesQuery(pub MediaKind);
+
+impl HTTPQuery for DirectoriesQuery {
+    type Response = DirectoriesData;
+
+    fn path(&self) -> String {
+        A8MiniComplexHTTPQuery::GetDirectories(self.0).path()
+    }
+}
+
+/// Counts the files of a kind. Typed form of `A8MiniComplexHTTPQuery::GetMediaCount`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MediaCountQuery(pub MediaKind);
+
+impl HTTPQuery for MediaCountQuery {
+    type Response = MediaCountData;
+
+    fn path(&self) -> String {
+        A8MiniComplexHTTPQuery::GetMediaCount(self.0).path()
+    }
+}
+
+/// Lists `count` files of a kind from index `start`. Typed form of
+/// `A8MiniComplexHTTPQuery::GetMediaList`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MediaListQuery {
+    pub kind: MediaKind,
+    pub start: u32,
+    pub count: u32,
+}
+
+impl HTTPQuery for MediaListQuery {
+    type Response = MediaListData;
+
+    fn path(&self) -> String {
+        A8MiniComplexHTTPQuery::GetMediaList { kind: self.kind, start: self.start, count: self.count }.path()
+    }
+}
+
+/// `data` of a `DirectoriesQuery` answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirectoriesData {
+    pub media_type: i32,
+    #[serde(default)]
+    pub directories: Vec<MediaDirectory>,
+}
+
+/// `data` of a `MediaCountQuery` answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MediaCountData {
+    pub media_type: i32,
+    #[serde(default)]
+    pub path: String,
+    pub count: i32,
+}
+
+/// `data` of a `MediaListQuery` answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MediaListData {
+    pub media_type: i32,
+    #[serde(default)]
+    pub path: String,
+    #[serde(default)]
+    pub list: Vec<MediaEntry>,
+}
+
+/// Response json format, with `data` shaped by the query (see `HTTPQuery::Response`).
 #[derive(Debug, Serialize, Deserialize)]
-pub struct HTTPResponse {
+pub struct HTTPResponse<D = HTTPResponseData> {
     pub code: i32,
-    pub data: HTTPResponseData,
+    pub data: D,
     pub success: bool,
     pub message: String,
 }
 
-/// Response json data format
+impl<D: DeserializeOwned> HTTPResponse<D> {
+    /// Decodes the body of a response from `url`, keeping the start of the body in the error if
+    /// it isn't a valid `HTTPResponse`.
+    pub fn from_body(url: &str, body: &[u8]) -> Result<Self, A8MiniError> {
+        serde_json::from_slice(body).map_err(|_| {
+            let snippet = &body[..body.len().min(constants::BODY_SNIPPET_LEN)];
+            A8MiniError::MalformedResponse {
+                url: url.to_string(),
+                body_snippet: String::from_utf8_lossy(snippet).into_owned(),
+            }
+        })
+    }
+}
+
+impl HTTPResponse {
+    /// Media directories returned by a `GetDirectories*` query. Empty if the field is absent.
+    pub fn directories(&self) -> Vec<MediaDirectory> {
+        self.data.directories.clone().unwrap_or_default()
+    }
+
+    /// Media files returned by a `GetMediaList` query. Empty if the field is absent.
+    pub fn entries(&self) -> Vec<MediaEntry> {
+        self.data.list.clone().unwrap_or_default()
+    }
+}
+
+/// Legacy response json data format of the `A8MiniSimpleHTTPQuery` and `A8MiniComplexHTTPQuery`
+/// endpoints, with every field any of them returns. Each enum answers for endpoints of several
+/// shapes, so they keep this catch-all; new code should use the typed queries (`DirectoriesQuery`,
+/// `MediaCountQuery`, `MediaListQuery`), whose `data` is a struct of just that endpoint's fields.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct HTTPResponseData {
     pub media_type: i32,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub directories: Option<String>,
+    pub directories: Option<Vec<MediaDirectory>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -204,13 +1045,206 @@ pub struct HTTPResponseData {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub count: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub list: Option<String>,
+    pub list: Option<Vec<MediaEntry>>,
 }
 
-#[derive(Debug, PartialEq, Eq, Deserialize)]
-pub struct A8MiniFirmwareVersion {
-    // Camera Code Version (Bytes 8-11)
-    pub code_ver_byte0: u8, // Patch
+/// Media folder on the camera's SD card, as listed by `GetDirectories*`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MediaDirectory {
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<i32>,
+}
+
+/// Media file on the camera's SD card, as listed by `GetMediaList`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MediaEntry {
+    pub name: String,
+    /// Direct download URL served by the camera.
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+}
+
+/// Parsed SIYI frame received from the camera in response to a `Command`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AckFrame {
+    pub control: u8,
+    pub length: u16,
+    pub sequence: u16,
+    pub command_id: u8,
+    pub payload: Vec<u8>,
+    pub crc: u16,
+}
+
+impl AckFrame {
+    /// Size of the STX + CTRL + DATALEN + SEQ + CMD_ID header.
+    pub const HEADER_LEN: usize = 8;
+    /// Size of the CRC16 trailer.
+    pub const CRC_LEN: usize = 2;
+
+    /// Parses a frame, validating the STX mark, the declared data length and the CRC16.
+    /// Any bytes past the end of the declared frame are ignored.
+    pub fn parse(frame: &[u8]) -> Result<AckFrame, A8MiniError> {
+        Self::parse_with_crc_init(frame, constants::CRC16_INIT)
+    }
+
+    /// Same as `parse`, for firmware whose CRC starts from `crc_init`.
+    pub fn parse_with_crc_init(frame: &[u8], crc_init: u16) -> Result<AckFrame, A8MiniError> {
+        let (command_id, payload) = Self::validate_with_crc_init(frame, crc_init)?;
+        let crc_start = Self::HEADER_LEN + payload.len();
+
+        Ok(AckFrame {
+            control: frame[2],
+            length: payload.len() as u16,
+            sequence: bytes::read_u16_le(frame, 5),
+            command_id,
+            payload: payload.to_vec(),
+            crc: bytes::read_u16_le(frame, crc_start),
+        })
+    }
+
+    /// Whether both frames carry the same command: equal control byte, command ID and payload.
+    /// The sequence number and CRC, which differ between sends of one command, are ignored.
+    pub fn same_command(&self, other: &AckFrame) -> bool {
+        self.control == other.control && self.command_id == other.command_id && self.payload == other.payload
+    }
+
+    /// Offset of the status byte in the ACK payload of commands that report success, `None` for
+    /// the rest. 1 is success; anything else is a rejection.
+    fn status_offset(command_id: u8) -> Option<usize> {
+        match command_id {
+            // stream type, then status
+            0x21 => Some(1),
+            0x04 | 0x06 | 0x07 | 0x08 | 0x0F | 0x30 => Some(0),
+            _ => None,
+        }
+    }
+
+    /// Fails with `A8MiniError::CameraNack` if this is the ACK of a command that reports
+    /// success and the camera rejected it. ACKs without a status always pass.
+    pub fn check_status(&self) -> Result<(), A8MiniError> {
+        let Some(offset) = Self::status_offset(self.command_id) else {
+            return Ok(());
+        };
+        let code = *self.payload.get(offset).ok_or(A8MiniError::FrameTooShort {
+            expected: offset + 1,
+            actual: self.payload.len(),
+        })?;
+        if code != 1 {
+            return Err(A8MiniError::CameraNack { command_id: self.command_id, code });
+        }
+
+        Ok(())
+    }
+
+    /// Validates a frame like `parse` and returns its command ID and payload without copying,
+    /// for hot paths that decode the payload straight away.
+    pub fn validate(frame: &[u8]) -> Result<(u8, &[u8]), A8MiniError> {
+        Self::validate_with_crc_init(frame, constants::CRC16_INIT)
+    }
+
+    /// Same as `validate`, for firmware whose CRC starts from `crc_init`.
+    pub fn validate_with_crc_init(frame: &[u8], crc_init: u16) -> Result<(u8, &[u8]), A8MiniError> {
+        if frame.len() < Self::HEADER_LEN + Self::CRC_LEN {
+            return Err(A8MiniError::FrameTooShort {
+                expected: Self::HEADER_LEN + Self::CRC_LEN,
+                actual: frame.len(),
+            });
+        }
+
+        if frame[0..2] != [0x55, 0x66] {
+            return Err(A8MiniError::InvalidHeader([frame[0], frame[1]]));
+        }
+
+        let length = bytes::read_u16_le(frame, 3);
+        let frame_len = Self::HEADER_LEN + length as usize + Self::CRC_LEN;
+        if frame.len() < frame_len {
+            return Err(A8MiniError::FrameTooShort {
+                expected: frame_len,
+                actual: frame.len(),
+            });
+        }
+
+        let crc_start = frame_len - Self::CRC_LEN;
+        let crc = bytes::read_u16_le(frame, crc_start);
+        let expected_crc = u16::from_le_bytes(checksum::crc16_calc(&frame[..crc_start], crc_init));
+        if crc != expected_crc {
+            return Err(A8MiniError::CrcMismatch {
+                expected: expected_crc,
+                actual: crc,
+            });
+        }
+
+        Ok((frame[7], &frame[Self::HEADER_LEN..crc_start]))
+    }
+}
+
+/// Typed contents of an `AckFrame`, chosen by its command ID.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AckPayload {
+    Firmware(A8MiniFirmwareVersion),
+    /// Hardware ID string, with trailing NULs removed.
+    HardwareId(String),
+    GimbalStatus(GimbalStatus),
+    Attitude(A8MiniAttitude),
+    /// Yaw, pitch and roll in tenths of a degree, as ACKed by `SetYawPitchAngle`.
+    Angles(i16, i16, i16),
+    /// Distance in tenths of a metre.
+    LaserRange(u16),
+    MaxZoom(f32),
+    Zoom(f32),
+    CodecSpecs(CodecSpecs),
+    /// Whether the camera accepted the command.
+    Status(bool),
+    /// ACK of a command this crate doesn't decode.
+    Raw { command_id: u8, payload: Vec<u8> },
+}
+
+impl AckPayload {
+    /// Decodes `frame`'s payload with the decoder for its command ID.
+    pub fn from_frame(frame: &AckFrame) -> Result<Self, A8MiniError> {
+        let payload = frame.payload.as_slice();
+        let too_short = |expected: usize| A8MiniError::FrameTooShort { expected, actual: payload.len() };
+
+        Ok(match frame.command_id {
+            0x01 => AckPayload::Firmware(A8MiniFirmwareVersion::from_payload(payload)?),
+            0x02 => AckPayload::HardwareId(hardware_id_from_payload(payload)),
+            0x0A => AckPayload::GimbalStatus(GimbalStatus::from_payload(payload)?),
+            0x0D => AckPayload::Attitude(A8MiniAttitude::from_payload(payload)?),
+            0x0E => {
+                if payload.len() < 6 {
+                    return Err(too_short(6));
+                }
+                let angle = |i: usize| bytes::read_i16_le(payload, 2 * i);
+                AckPayload::Angles(angle(0), angle(1), angle(2))
+            }
+            0x15 => {
+                if payload.len() < 2 {
+                    return Err(too_short(2));
+                }
+                AckPayload::LaserRange(bytes::read_u16_le(payload, 0))
+            }
+            0x16 => AckPayload::MaxZoom(zoom_from_payload(payload)?),
+            0x18 => AckPayload::Zoom(zoom_from_payload(payload)?),
+            0x20 => AckPayload::CodecSpecs(CodecSpecs::from_payload(payload)?),
+            command_id => match AckFrame::status_offset(command_id) {
+                Some(offset) => AckPayload::Status(*payload.get(offset).ok_or(too_short(offset + 1))? == 1),
+                None => AckPayload::Raw { command_id, payload: payload.to_vec() },
+            },
+        })
+    }
+}
+
+/// Reads the hardware ID string of a `0x02` ACK payload, dropping trailing NULs.
+pub fn hardware_id_from_payload(payload: &[u8]) -> String {
+    String::from_utf8_lossy(payload).trim_end_matches('\0').to_string()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct A8MiniFirmwareVersion {
+    // Camera Code Version (Bytes 8-11)
+    pub code_ver_byte0: u8, // Patch
     pub code_ver_byte1: u8, // Minor
     pub code_ver_byte2: u8, // Major
     pub code_ver_byte3: u8, // Rev
@@ -222,6 +1256,31 @@ pub struct A8MiniFirmwareVersion {
     pub gimbal_ver_byte3: u8, // Rev   (e.g. 115)
 }
 
+impl A8MiniFirmwareVersion {
+    /// Size of the camera and gimbal versions at the start of a `0x01` ACK payload.
+    pub const PAYLOAD_LEN: usize = 8;
+
+    /// Decodes the versions from a `0x01` ACK payload. The zoom version that follows is ignored.
+    pub fn from_payload(payload: &[u8]) -> Result<Self, A8MiniError> {
+        let fields = payload.get(..Self::PAYLOAD_LEN).ok_or(A8MiniError::FrameTooShort {
+            expected: Self::PAYLOAD_LEN,
+            actual: payload.len(),
+        })?;
+        // only fails on short input, ruled out above
+        Ok(bincode::deserialize(fields).expect("firmware version fields are plain bytes"))
+    }
+
+    /// Gimbal firmware as `(major, minor, patch)`, ordered for comparison.
+    pub fn gimbal_version(&self) -> (u8, u8, u8) {
+        (self.gimbal_ver_byte2, self.gimbal_ver_byte1, self.gimbal_ver_byte0)
+    }
+
+    /// Camera firmware as `(major, minor, patch)`.
+    pub fn camera_version(&self) -> (u8, u8, u8) {
+        (self.code_ver_byte2, self.code_ver_byte1, self.code_ver_byte0)
+    }
+}
+
 impl fmt::Display for A8MiniFirmwareVersion {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Display format: Major.Minor.Patch
@@ -235,8 +1294,364 @@ impl fmt::Display for A8MiniFirmwareVersion {
         )
     }
 }
+
+/// Video codec, with the byte values the camera uses for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VideoCodec {
+    H264 = 1,
+    H265 = 2,
+}
+
+impl VideoCodec {
+    /// Codec for a byte as sent by the camera, or `None` for unknown values.
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            1 => Some(VideoCodec::H264),
+            2 => Some(VideoCodec::H265),
+            _ => None,
+        }
+    }
+}
+
+/// Video resolutions the A8 mini encoder supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Resolution {
+    Hd720,
+    Hd1080,
+    Uhd4k,
+}
+
+impl Resolution {
+    /// Width and height in pixels.
+    pub fn dimensions(&self) -> (u16, u16) {
+        match *self {
+            Resolution::Hd720 => (1280, 720),
+            Resolution::Hd1080 => (1920, 1080),
+            Resolution::Uhd4k => (3840, 2160),
+        }
+    }
+
+    /// Resolution with these dimensions, or `None` if the encoder doesn't support them.
+    pub fn from_dimensions(width: u16, height: u16) -> Option<Self> {
+        [Resolution::Hd720, Resolution::Hd1080, Resolution::Uhd4k]
+            .into_iter()
+            .find(|resolution| resolution.dimensions() == (width, height))
+    }
+}
+
+/// Validated settings for `SetCodecSpecs`. Start from a named resolution and adjust the stream
+/// and codec as needed:
+/// `CodecConfig::fhd_1080p(4000).stream_type(1).codec(VideoCodec::H264).to_command()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodecConfig {
+    pub stream_type: u8,
+    pub codec: VideoCodec,
+    pub width: u16,
+    pub height: u16,
+    pub bitrate_kbps: u16,
+}
+
+impl CodecConfig {
+    /// Resolutions the A8 mini encoder accepts.
+    pub const SUPPORTED_RESOLUTIONS: [(u16, u16); 3] = [(3840, 2160), (1920, 1080), (1280, 720)];
+    pub const MIN_BITRATE_KBPS: u16 = 1000;
+    pub const MAX_BITRATE_KBPS: u16 = 20000;
+
+    /// 3840x2160 H.265 on the recording stream.
+    pub fn uhd_4k(bitrate_kbps: u16) -> Self {
+        Self::with_resolution(3840, 2160, bitrate_kbps)
+    }
+
+    /// 1920x1080 H.265 on the recording stream.
+    pub fn fhd_1080p(bitrate_kbps: u16) -> Self {
+        Self::with_resolution(1920, 1080, bitrate_kbps)
+    }
+
+    /// 1280x720 H.265 on the recording stream.
+    pub fn hd_720p(bitrate_kbps: u16) -> Self {
+        Self::with_resolution(1280, 720, bitrate_kbps)
+    }
+
+    fn with_resolution(width: u16, height: u16, bitrate_kbps: u16) -> Self {
+        CodecConfig {
+            stream_type: 0,
+            codec: VideoCodec::H265,
+            width,
+            height,
+            bitrate_kbps,
+        }
+    }
+
+    /// Defaults for the recording, main and sub streams, in that order, all H.265. Only the
+    /// recording stream's 4K at 15 Mbps comes from SIYI (the SDK's `SetCodecSpecs` example frame,
+    /// `A8MiniSimpleCommand::Resolution4k`). SIYI documents no main or sub stream defaults; their
+    /// 1080p at 4 Mbps and 720p at 2 Mbps are assumed values, not read from a factory-fresh camera.
+    pub fn factory_defaults() -> [CodecConfig; 3] {
+        [
+            CodecConfig::uhd_4k(15000),
+            CodecConfig::fhd_1080p(4000).stream_type(1),
+            CodecConfig::hd_720p(2000).stream_type(2),
+        ]
+    }
+
+    /// 0: recording stream, 1: main stream, 2: sub stream
+    pub fn stream_type(mut self, stream_type: u8) -> Self {
+        self.stream_type = stream_type;
+        self
+    }
+
+    pub fn resolution(mut self, resolution: Resolution) -> Self {
+        (self.width, self.height) = resolution.dimensions();
+        self
+    }
+
+    pub fn codec(mut self, codec: VideoCodec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// Checks the combination against what the camera supports.
+    pub fn validate(&self) -> Result<(), A8MiniError> {
+        let supported = self.stream_type <= 2
+            && Self::SUPPORTED_RESOLUTIONS.contains(&(self.width, self.height))
+            && (Self::MIN_BITRATE_KBPS..=Self::MAX_BITRATE_KBPS).contains(&self.bitrate_kbps);
+
+        if !supported {
+            return Err(A8MiniError::UnsupportedCodecConfig {
+                stream_type: self.stream_type,
+                codec: self.codec as u8,
+                width: self.width,
+                height: self.height,
+                bitrate_kbps: self.bitrate_kbps,
+            });
+        }
+        Ok(())
+    }
+
+    /// Validates and converts into the raw `SetCodecSpecs` command.
+    pub fn to_command(&self) -> Result<A8MiniComplexCommand, A8MiniError> {
+        self.validate()?;
+        Ok(A8MiniComplexCommand::SetCodecSpecs(
+            self.stream_type,
+            self.codec as u8,
+            self.width,
+            self.height,
+            self.bitrate_kbps,
+            0,
+        ))
+    }
+}
+
+/// Gimbal motion mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GimbalMode {
+    Lock,
+    Follow,
+    Fpv,
+}
+
+impl GimbalMode {
+    /// Command switching the gimbal to this mode.
+    pub fn command(&self) -> A8MiniSimpleCommand {
+        match *self {
+            GimbalMode::Lock => A8MiniSimpleCommand::SetLockMode,
+            GimbalMode::Follow => A8MiniSimpleCommand::SetFollowMode,
+            GimbalMode::Fpv => A8MiniSimpleCommand::SetFPVMode,
+        }
+    }
+
+    /// Mode for a `GimbalStatus::motion_mode` value.
+    pub fn from_motion_mode(motion_mode: u8) -> Option<Self> {
+        match motion_mode {
+            0 => Some(GimbalMode::Lock),
+            1 => Some(GimbalMode::Follow),
+            2 => Some(GimbalMode::Fpv),
+            _ => None,
+        }
+    }
+}
+
+/// How the gimbal is mounted, as reported in `GimbalStatus`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MountingDirection {
+    Normal,
+    UpsideDown,
+}
+
+impl MountingDirection {
+    /// Direction for a `GimbalStatus::mounting_direction` value. 0 is reserved.
+    pub fn from_raw(mounting_direction: u8) -> Option<Self> {
+        match mounting_direction {
+            1 => Some(MountingDirection::Normal),
+            2 => Some(MountingDirection::UpsideDown),
+            _ => None,
+        }
+    }
+}
+
+/// Whether the camera is recording, and for how long, as returned by `A8Mini::get_recording_state`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordingState {
+    pub active: bool,
+    /// Seconds since this client first saw the recording running. 0 when idle.
+    pub elapsed_secs: u32,
+}
+
+/// Gimbal configuration, as returned by `GimbalStatus`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GimbalStatus {
+    /// 0: HDR off, 1: HDR on
+    pub hdr: u8,
+    /// 0: not recording, 1: recording, 2: no TF card, 3: recording data lost
+    pub record_status: u8,
+    /// 0: lock, 1: follow, 2: FPV
+    pub motion_mode: u8,
+    /// 0: reserved, 1: normal, 2: upside down
+    pub mounting_direction: u8,
+    /// 0: HDMI, 1: CVBS
+    pub video_output: u8,
+}
+
+impl GimbalStatus {
+    /// Size of the status fields in a `0x0A` ACK payload.
+    pub const PAYLOAD_LEN: usize = 7;
+
+    /// Decodes the status from a `0x0A` ACK payload. Trailing bytes are ignored.
+    pub fn from_payload(payload: &[u8]) -> Result<Self, A8MiniError> {
+        let fields = payload.get(..Self::PAYLOAD_LEN).ok_or(A8MiniError::FrameTooShort {
+            expected: Self::PAYLOAD_LEN,
+            actual: payload.len(),
+        })?;
+
+        // bytes 0 and 2 are reserved
+        Ok(GimbalStatus {
+            hdr: fields[1],
+            record_status: fields[3],
+            motion_mode: fields[4],
+            mounting_direction: fields[5],
+            video_output: fields[6],
+        })
+    }
+
+    /// Whether a TF (SD) card is inserted. The camera exposes no free/total space, so this is
+    /// the only storage check available.
+    pub fn sd_card_present(&self) -> bool {
+        self.record_status != 2
+    }
+
+    /// Current motion mode, or `None` for values this crate doesn't know.
+    pub fn mode(&self) -> Option<GimbalMode> {
+        GimbalMode::from_motion_mode(self.motion_mode)
+    }
+
+    /// Mounting direction, or `None` for the reserved value.
+    pub fn mounting(&self) -> Option<MountingDirection> {
+        MountingDirection::from_raw(self.mounting_direction)
+    }
+}
+
+/// Camera product, identified by the first two characters of its hardware ID.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CameraModel {
+    A8Mini,
+    A2Mini,
+    Zr10,
+    Zr30,
+    Zt6,
+    Zt30,
+    /// Model code this crate doesn't know, e.g. a newer SIYI camera.
+    Unknown(String),
+}
+
+impl CameraModel {
+    /// Identifies the model from a `HardwareIDInformation` (`0x02`) ACK payload.
+    pub fn from_hardware_id(payload: &[u8]) -> Self {
+        match payload.get(..2) {
+            Some(b"73") => CameraModel::A8Mini,
+            Some(b"75") => CameraModel::A2Mini,
+            Some(b"6B") => CameraModel::Zr10,
+            Some(b"78") => CameraModel::Zr30,
+            Some(b"82") => CameraModel::Zt6,
+            Some(b"7A") => CameraModel::Zt30,
+            code => CameraModel::Unknown(String::from_utf8_lossy(code.unwrap_or(payload)).into_owned()),
+        }
+    }
+}
+
+/// What the camera reports about itself, as returned by `A8Mini::get_camera_info`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CameraInfo {
+    pub model: CameraModel,
+    /// Hardware ID string, with trailing NULs removed.
+    pub hardware_id: String,
+    /// Whether HDR is enabled. The protocol has no separate HDR capability flag.
+    pub hdr: bool,
+    /// Resolution of the main stream, or `None` for dimensions this crate doesn't know.
+    pub main_stream_resolution: Option<Resolution>,
+    /// Resolution of the sub stream, or `None` for dimensions this crate doesn't know.
+    pub sub_stream_resolution: Option<Resolution>,
+}
+
+/// SD card state, as returned by `A8Mini::get_storage_info`. The camera doesn't report free or
+/// total space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageInfo {
+    pub sd_card_present: bool,
+    /// Files on the card, or `None` if the HTTP API didn't answer.
+    pub photo_count: Option<i32>,
+    pub video_count: Option<i32>,
+}
+
+/// Everything a dashboard polls, as returned by `A8Mini::health`. Each field is `None` if its
+/// query failed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CameraHealth {
+    pub gimbal_status: Option<GimbalStatus>,
+    pub attitude: Option<A8MiniAttitude>,
+    pub storage: Option<StorageInfo>,
+    pub firmware: Option<A8MiniFirmwareVersion>,
+}
+
+/// Video encoding parameters of one camera stream, as returned by `GetCodecSpecs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodecSpecs {
+    /// 0: recording stream, 1: main stream, 2: sub stream
+    pub stream_type: u8,
+    /// 1: H.264, 2: H.265
+    pub codec: u8,
+    pub width: u16,
+    pub height: u16,
+    pub bitrate_kbps: u16,
+    pub framerate: u8,
+}
+
+impl CodecSpecs {
+    /// Size of a `0x20` ACK payload.
+    pub const PAYLOAD_LEN: usize = 9;
+
+    /// Decodes the specs from a `0x20` ACK payload.
+    pub fn from_payload(payload: &[u8]) -> Result<Self, A8MiniError> {
+        let fields = payload.get(..Self::PAYLOAD_LEN).ok_or(A8MiniError::FrameTooShort {
+            expected: Self::PAYLOAD_LEN,
+            actual: payload.len(),
+        })?;
+        // only fails on short input, ruled out above
+        Ok(bincode::deserialize(fields).expect("codec spec fields are fixed-size integers"))
+    }
+
+    /// Stream resolution, or `None` for dimensions this crate doesn't know.
+    pub fn resolution(&self) -> Option<Resolution> {
+        Resolution::from_dimensions(self.width, self.height)
+    }
+
+    /// `codec` as a `VideoCodec`, or `None` for values this crate doesn't know.
+    pub fn video_codec(&self) -> Option<VideoCodec> {
+        VideoCodec::from_byte(self.codec)
+    }
+}
+
 /// Camera attitude information
-#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct A8MiniAttitude {
     pub theta_yaw: i16,
     pub theta_pitch: i16,
@@ -246,16 +1661,122 @@ pub struct A8MiniAttitude {
     pub v_roll: i16,
 }
 
+impl A8MiniAttitude {
+    /// Size of the attitude fields at the start of a `0x0D` ACK payload.
+    pub const PAYLOAD_LEN: usize = 12;
+
+    /// The attitude as seen from an upright frame when reported by an upside-down gimbal:
+    /// flipping the mount over the roll axis reverses yaw and pitch, leaving roll as is.
+    pub fn inverted(self) -> Self {
+        A8MiniAttitude {
+            theta_yaw: self.theta_yaw.saturating_neg(),
+            theta_pitch: self.theta_pitch.saturating_neg(),
+            v_yaw: self.v_yaw.saturating_neg(),
+            v_pitch: self.v_pitch.saturating_neg(),
+            ..self
+        }
+    }
+
+    /// Decodes the attitude from a `0x0D` ACK payload. Bytes past the first 12 (extended data
+    /// from newer firmware) are ignored.
+    pub fn from_payload(payload: &[u8]) -> Result<Self, A8MiniError> {
+        let fields = payload.get(..Self::PAYLOAD_LEN).ok_or(A8MiniError::FrameTooShort {
+            expected: Self::PAYLOAD_LEN,
+            actual: payload.len(),
+        })?;
+        let word = |i: usize| bytes::read_i16_le(fields, 2 * i);
+
+        Ok(A8MiniAttitude {
+            theta_yaw: word(0),
+            theta_pitch: word(1),
+            theta_roll: word(2),
+            v_yaw: word(3),
+            v_pitch: word(4),
+            v_roll: word(5),
+        })
+    }
+
+    /// Yaw in degrees. Raw values are in tenths of a degree.
+    pub fn yaw_deg(&self) -> f32 {
+        self.theta_yaw as f32 / 10.0
+    }
+
+    /// Pitch in degrees.
+    pub fn pitch_deg(&self) -> f32 {
+        self.theta_pitch as f32 / 10.0
+    }
+
+    /// Roll in degrees.
+    pub fn roll_deg(&self) -> f32 {
+        self.theta_roll as f32 / 10.0
+    }
+
+    /// Yaw, pitch and roll angular velocities in degrees per second.
+    pub fn angular_velocity_deg(&self) -> [f32; 3] {
+        [
+            self.v_yaw as f32 / 10.0,
+            self.v_pitch as f32 / 10.0,
+            self.v_roll as f32 / 10.0,
+        ]
+    }
+
+    /// Orientation as a unit quaternion `[w, x, y, z]`, composing yaw (about z), then pitch
+    /// (about y), then roll (about x).
+    pub fn to_quaternion(&self) -> [f32; 4] {
+        let (sy, cy) = (self.yaw_deg().to_radians() / 2.0).sin_cos();
+        let (sp, cp) = (self.pitch_deg().to_radians() / 2.0).sin_cos();
+        let (sr, cr) = (self.roll_deg().to_radians() / 2.0).sin_cos();
+
+        [
+            cr * cp * cy + sr * sp * sy,
+            sr * cp * cy - cr * sp * sy,
+            cr * sp * cy + sr * cp * sy,
+            cr * cp * sy - sr * sp * cy,
+        ]
+    }
+
+    /// Header of the attitude CSV log written by `A8Mini::log_attitude_csv`.
+    pub const CSV_HEADER: &'static str = "Timestamp,Yaw,Pitch,Roll,V_Yaw,V_Pitch,V_Roll\n";
+
+    /// One attitude CSV log row: RFC 3339 timestamp, angles in degrees, raw angular velocities.
+    pub fn to_csv_row(&self, timestamp: DateTime<Utc>) -> String {
+        format!(
+            "{},{},{},{},{},{},{}\n",
+            timestamp.to_rfc3339(),
+            self.yaw_deg(),
+            self.pitch_deg(),
+            self.roll_deg(),
+            self.v_yaw,
+            self.v_pitch,
+            self.v_roll
+        )
+    }
+
+    /// One attitude NDJSON log line: a JSON object with an RFC 3339 `timestamp` and the raw
+    /// fields, terminated by a newline.
+    pub fn to_ndjson_line(&self, timestamp: DateTime<Utc>) -> String {
+        #[derive(Serialize)]
+        struct Line<'a> {
+            timestamp: DateTime<Utc>,
+            #[serde(flatten)]
+            attitude: &'a A8MiniAttitude,
+        }
+
+        let mut line = serde_json::to_string(&Line { timestamp, attitude: self })
+            .expect("attitude serializes to JSON");
+        line.push('\n');
+        line
+    }
+}
+
 impl fmt::Display for A8MiniAttitude {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let yaw_deg = self.theta_yaw as f32 / 10.0;
-        let pitch_deg = self.theta_pitch as f32 / 10.0;
-        let roll_deg = self.theta_roll as f32 / 10.0;
+        let [v_yaw, v_pitch, v_roll] = self.angular_velocity_deg();
 
         write!(
             f,
-            "GIMBAL ATTITUDE:\n\tYaw:   {:.1}°\n\tPitch: {:.1}°\n\tRoll:  {:.1}°\n\t(Speeds: Y={}, P={}, R={})",
-            yaw_deg, pitch_deg, roll_deg, self.v_yaw, self.v_pitch, self.v_roll
+            "GIMBAL ATTITUDE:\n\tYaw:   {:.1}°\n\tPitch: {:.1}°\n\tRoll:  {:.1}°\n\t(Speeds: Y={:.1}°/s, P={:.1}°/s, R={:.1}°/s)",
+            self.yaw_deg(), self.pitch_deg(), self.roll_deg(), v_yaw, v_pitch, v_roll
         )
     }
 }
@@ -267,8 +1788,9 @@ mod tests {
     #[test]
     fn test_complex_command_creation_angle() {
         let computed_command = A8MiniComplexCommand::SetYawPitchAngle(130, -20).to_bytes();
+        // Angles are little endian, like every other multi-byte field in the protocol.
         let expected_command: [u8; 14] = [
-            0x55, 0x66, 0x01, 0x04, 0x00, 0x00, 0x00, 0x0e, 0x00, 0x82, 0xff, 0xec, 0x8f, 0xad,
+            0x55, 0x66, 0x01, 0x04, 0x00, 0x00, 0x00, 0x0e, 0x82, 0x00, 0xec, 0xff, 0x97, 0xbc,
         ];
         assert_eq!(computed_command, expected_command);
     }
@@ -302,4 +1824,900 @@ mod tests {
 
         assert_eq!(computed_attitude_info, expected_attitude_info);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_stamp_sequence() {
+        let mut frame = A8MiniSimpleCommand::AttitudeInformation.to_bytes();
+        stamp_sequence(&mut frame, 0x1234).unwrap();
+        assert_eq!(&frame[5..7], &[0x34, 0x12]);
+
+        let parsed = AckFrame::parse(&frame).unwrap();
+        assert_eq!(parsed.sequence, 0x1234);
+        assert_eq!(parsed.command_id, 0x0D);
+
+        assert_eq!(
+            stamp_sequence(&mut [0x55, 0x66, 0x01], 1),
+            Err(A8MiniError::FrameTooShort { expected: 10, actual: 3 })
+        );
+    }
+
+    #[test]
+    fn test_ack_frame_parse() {
+        // Attitude ACK: yaw 4.0, pitch 5.0, roll 6.0, speeds 4/5/6.
+        let mut bytes: Vec<u8> = vec![
+            0x55, 0x66, 0x02, 0x0c, 0x00, 0x07, 0x00, 0x0d, 0x28, 0x00, 0x32, 0x00, 0x3c, 0x00,
+            0x04, 0x00, 0x05, 0x00, 0x06, 0x00,
+        ];
+        let crc = checksum::crc16_calc(&bytes, 0);
+        bytes.extend_from_slice(&crc);
+        // trailing garbage past the declared frame is ignored
+        bytes.extend_from_slice(&[0x00, 0x00]);
+
+        let frame = AckFrame::parse(&bytes).unwrap();
+        assert_eq!(frame.control, 0x02);
+        assert_eq!(frame.length, 12);
+        assert_eq!(frame.sequence, 7);
+        assert_eq!(frame.command_id, 0x0d);
+        assert_eq!(frame.payload, bytes[8..20].to_vec());
+        assert_eq!(frame.crc, u16::from_le_bytes(crc));
+    }
+
+    #[test]
+    fn test_http_query_api_path() {
+        let list = A8MiniComplexHTTPQuery::GetMediaList { kind: MediaKind::Photo, start: 0, count: 2 };
+        assert_eq!(list.path_with_api_path(constants::MEDIA_API_PATH), list.path());
+        assert_eq!(
+            list.path_with_api_path("/media"),
+            "/media/api/v1/getmedialist?media_type=0&path=101SIYI_IMG&start=0&count=2"
+        );
+        assert_eq!(
+            A8MiniComplexHTTPQuery::GetMediaCount(MediaKind::Video).path_with_api_path("/cgi-bin/siyi.cgi"),
+            "/cgi-bin/siyi.cgi/api/v1/getmediacount?media_type=1&path=100SIYI_VID"
+        );
+        // downloads aren't under the media API
+        let photo = A8MiniComplexHTTPQuery::GetMedia(MediaKind::Photo, 3);
+        assert_eq!(photo.path_with_api_path("/media"), photo.path());
+    }
+
+    #[test]
+    fn test_moves_gimbal() {
+        assert!(A8MiniSimpleCommand::RotateLeft.moves_gimbal());
+        assert!(A8MiniSimpleCommand::AutoCenter.moves_gimbal());
+        assert!(!A8MiniSimpleCommand::StopRotation.moves_gimbal());
+        assert!(!A8MiniSimpleCommand::TakePicture.moves_gimbal());
+        assert!(A8MiniComplexCommand::SetYawPitchAngle(0, 0).moves_gimbal());
+        assert!(A8MiniComplexCommand::SetYawPitchSpeed(0, 10).moves_gimbal());
+        assert!(!A8MiniComplexCommand::SetYawPitchSpeed(0, 0).moves_gimbal());
+        assert!(A8MiniCommand::from(A8MiniSimpleCommand::RotateUp).moves_gimbal());
+        assert!(!A8MiniCommand::from(A8MiniComplexCommand::GetZoomLevel).moves_gimbal());
+    }
+
+    #[test]
+    fn test_external_command_defaults() {
+        // implements only `write_to`, with a frame longer than MAX_FRAME_LEN
+        #[derive(Debug)]
+        struct LongWrite;
+        impl Command for LongWrite {
+            fn write_to(&self, buf: &mut [u8]) -> Result<usize, A8MiniError> {
+                write_frame(buf, 0x7f, &[0xab; 40])
+            }
+        }
+
+        let frame = build_frame(0x7f, &[0xab; 40]);
+        let mut buf = [0u8; constants::MAX_FRAME_LEN];
+        assert_eq!(
+            LongWrite.write_to(&mut buf),
+            Err(A8MiniError::BufferTooSmall { needed: frame.len(), actual: constants::MAX_FRAME_LEN })
+        );
+        assert_eq!(LongWrite.try_to_bytes(), Ok(frame.clone()));
+        assert_eq!(LongWrite.to_bytes(), frame);
+    }
+
+    #[test]
+    fn test_ack_frame_same_command() {
+        let mut first = A8MiniSimpleCommand::AttitudeInformation.to_bytes();
+        let mut second = first.clone();
+        stamp_sequence(&mut first, 1).unwrap();
+        stamp_sequence(&mut second, 2).unwrap();
+        let first = AckFrame::parse(&first).unwrap();
+        let second = AckFrame::parse(&second).unwrap();
+        assert_ne!(first, second);
+        assert!(first.same_command(&second));
+
+        let other = AckFrame::parse(&A8MiniSimpleCommand::GimbalStatus.to_bytes()).unwrap();
+        assert!(!first.same_command(&other));
+        let zoom_in = AckFrame::parse(&A8MiniSimpleCommand::ZoomIn.to_bytes()).unwrap();
+        let zoom_out = AckFrame::parse(&A8MiniSimpleCommand::ZoomOut.to_bytes()).unwrap();
+        assert!(!zoom_in.same_command(&zoom_out));
+    }
+
+    #[test]
+    fn test_ack_frame_check_status() {
+        // codec specs ACK for the recording stream, status 0
+        let nack = AckFrame::parse(&[0x55, 0x66, 0x02, 0x02, 0x00, 0x00, 0x00, 0x21, 0x00, 0x00, 0xe2, 0xd8]).unwrap();
+        assert_eq!(nack.check_status(), Err(A8MiniError::CameraNack { command_id: 0x21, code: 0 }));
+
+        let ack = AckFrame { payload: vec![0x00, 0x01], ..nack.clone() };
+        assert_eq!(ack.check_status(), Ok(()));
+        let truncated = AckFrame { payload: vec![0x00], ..nack };
+        assert_eq!(truncated.check_status(), Err(A8MiniError::FrameTooShort { expected: 2, actual: 1 }));
+
+        // attitude ACKs carry no status
+        let attitude = AckFrame::parse(&A8MiniSimpleCommand::AttitudeInformation.to_bytes()).unwrap();
+        assert_eq!(attitude.check_status(), Ok(()));
+    }
+
+    #[test]
+    fn test_ack_frame_parse_errors() {
+        assert_eq!(
+            AckFrame::parse(&[0x55, 0x66, 0x02]),
+            Err(A8MiniError::FrameTooShort { expected: 10, actual: 3 })
+        );
+        assert_eq!(
+            AckFrame::parse(&[0x66, 0x55, 0x02, 0x00, 0x00, 0x00, 0x00, 0x0d, 0x00, 0x00]),
+            Err(A8MiniError::InvalidHeader([0x66, 0x55]))
+        );
+
+        // declares 12 data bytes but only carries 2
+        let truncated = [0x55, 0x66, 0x02, 0x0c, 0x00, 0x00, 0x00, 0x0d, 0x28, 0x00, 0x00, 0x00];
+        assert_eq!(
+            AckFrame::parse(&truncated),
+            Err(A8MiniError::FrameTooShort { expected: 22, actual: 12 })
+        );
+
+        let mut corrupted = constants::HARDCODED_COMMANDS[0].to_vec();
+        corrupted[8] ^= 0xff;
+        assert!(matches!(
+            AckFrame::parse(&corrupted),
+            Err(A8MiniError::CrcMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_http_response_directories() {
+        let response: HTTPResponse = serde_json::from_str(
+            r#"{"code":200,"data":{"media_type":0,"directories":[{"path":"101SIYI_IMG","count":3},{"path":"102SIYI_IMG"}]},"success":true,"message":""}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            response.directories(),
+            vec![
+                MediaDirectory { path: "101SIYI_IMG".to_string(), count: Some(3) },
+                MediaDirectory { path: "102SIYI_IMG".to_string(), count: None },
+            ]
+        );
+
+        let response: HTTPResponse = serde_json::from_str(
+            r#"{"code":200,"data":{"media_type":1},"success":true,"message":""}"#,
+        )
+        .unwrap();
+        assert!(response.directories().is_empty());
+    }
+
+    #[test]
+    fn test_http_response_from_malformed_body() {
+        let url = "http://192.168.144.25:82/cgi-bin/media.cgi/api/v1/getdirectories?media_type=0";
+        let html = format!("<html><body>502 Bad Gateway</body></html>{}", " ".repeat(300));
+        assert_eq!(
+            <HTTPResponse>::from_body(url, html.as_bytes()).unwrap_err(),
+            A8MiniError::MalformedResponse {
+                url: url.to_string(),
+                body_snippet: html[..constants::BODY_SNIPPET_LEN].to_string(),
+            }
+        );
+
+        let truncated = br#"{"code":200,"data":{"media_type":0,"#;
+        assert!(matches!(
+            <HTTPResponse>::from_body(url, truncated),
+            Err(A8MiniError::MalformedResponse { body_snippet, .. }) if body_snippet.as_bytes() == truncated
+        ));
+
+        let valid = br#"{"code":200,"data":{"media_type":1},"success":true,"message":""}"#;
+        assert_eq!(<HTTPResponse>::from_body(url, valid).unwrap().code, 200);
+    }
+
+    #[test]
+    fn test_media_list_query() {
+        let query = A8MiniComplexHTTPQuery::GetMediaList { kind: MediaKind::Video, start: 5, count: 10 };
+        assert_eq!(
+            query.path(),
+            "/cgi-bin/media.cgi/api/v1/getmedialist?media_type=1&path=100SIYI_VID&start=5&count=10"
+        );
+
+        let response: HTTPResponse = serde_json::from_str(
+            r#"{"code":200,"data":{"media_type":0,"list":[{"name":"IMG_0001.jpg","url":"http://192.168.144.25:82/photo/101SIYI_IMG/IMG_0001.jpg","size":1024}]},"success":true,"message":""}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            response.entries(),
+            vec![MediaEntry {
+                name: "IMG_0001.jpg".to_string(),
+                url: "http://192.168.144.25:82/photo/101SIYI_IMG/IMG_0001.jpg".to_string(),
+                size: Some(1024),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_typed_http_queries() {
+        assert_eq!(DirectoriesQuery(MediaKind::Video).path(), A8MiniComplexHTTPQuery::GetDirectories(MediaKind::Video).path());
+        assert_eq!(MediaCountQuery(MediaKind::Photo).path(), MediaKind::Photo.count_query().path());
+        assert_eq!(
+            MediaListQuery { kind: MediaKind::Photo, start: 2, count: 3 }.path(),
+            "/cgi-bin/media.cgi/api/v1/getmedialist?media_type=0&path=101SIYI_IMG&start=2&count=3"
+        );
+    }
+
+    #[test]
+    fn test_typed_http_responses() {
+        let url = "http://192.168.144.25:82/";
+        let directories = HTTPResponse::<DirectoriesData>::from_body(
+            url,
+            br#"{"code":200,"data":{"media_type":1,"directories":[{"path":"100SIYI_VID","count":2}]},"success":true,"message":""}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            directories.data.directories,
+            vec![MediaDirectory { path: "100SIYI_VID".to_string(), count: Some(2) }]
+        );
+
+        let count = HTTPResponse::<MediaCountData>::from_body(
+            url,
+            br#"{"code":200,"data":{"media_type":0,"path":"101SIYI_IMG","count":42},"success":true,"message":""}"#,
+        )
+        .unwrap();
+        assert_eq!(count.data, MediaCountData { media_type: 0, path: "101SIYI_IMG".to_string(), count: 42 });
+        // a count answer without a count is malformed rather than a `None` to check
+        let missing = br#"{"code":200,"data":{"media_type":0},"success":true,"message":""}"#;
+        assert!(matches!(
+            HTTPResponse::<MediaCountData>::from_body(url, missing),
+            Err(A8MiniError::MalformedResponse { .. })
+        ));
+
+        let list = HTTPResponse::<MediaListData>::from_body(
+            url,
+            br#"{"code":200,"data":{"media_type":0,"path":"101SIYI_IMG","list":[{"name":"IMG_0001.jpg","url":"u"}]},"success":true,"message":""}"#,
+        )
+        .unwrap();
+        assert_eq!(list.data.list, vec![MediaEntry { name: "IMG_0001.jpg".to_string(), url: "u".to_string(), size: None }]);
+        let empty = br#"{"code":200,"data":{"media_type":0},"success":true,"message":""}"#;
+        assert!(HTTPResponse::<MediaListData>::from_body(url, empty).unwrap().data.list.is_empty());
+    }
+
+    #[test]
+    fn test_media_by_name_query() {
+        assert_eq!(
+            A8MiniComplexHTTPQuery::GetPhotoByName("IMG_0007.jpg".to_string()).path(),
+            "/photo/101SIYI_IMG/IMG_0007.jpg"
+        );
+        assert_eq!(
+            A8MiniComplexHTTPQuery::GetVideoByName("REC 0001 (1)#&.mp4".to_string()).path(),
+            "/photo/100SIYI_VID/REC%200001%20%281%29%23%26.mp4"
+        );
+        assert_eq!(
+            A8MiniComplexHTTPQuery::GetPhotoByName("../é.jpg".to_string()).path(),
+            "/photo/101SIYI_IMG/..%2F%C3%A9.jpg"
+        );
+    }
+
+    #[test]
+    fn test_mounting_direction() {
+        let upright = GimbalStatus::from_payload(&[0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00]).unwrap();
+        assert_eq!(upright.mounting(), Some(MountingDirection::Normal));
+        let inverted = GimbalStatus { mounting_direction: 2, ..upright };
+        assert_eq!(inverted.mounting(), Some(MountingDirection::UpsideDown));
+        assert_eq!(GimbalStatus { mounting_direction: 0, ..upright }.mounting(), None);
+
+        let attitude = A8MiniAttitude { theta_yaw: 300, theta_pitch: -450, theta_roll: 20, v_yaw: -5, v_pitch: 7, v_roll: 1 };
+        assert_eq!(
+            attitude.inverted(),
+            A8MiniAttitude { theta_yaw: -300, theta_pitch: 450, theta_roll: 20, v_yaw: 5, v_pitch: -7, v_roll: 1 }
+        );
+        assert_eq!(attitude.inverted().inverted(), attitude);
+    }
+
+    #[test]
+    fn test_commands_as_map_keys() {
+        use std::collections::{HashMap, HashSet};
+
+        let mut last_sent: HashMap<A8MiniSimpleCommand, u32> = HashMap::new();
+        *last_sent.entry(A8MiniSimpleCommand::ZoomIn).or_default() += 1;
+        *last_sent.entry(A8MiniSimpleCommand::ZoomIn).or_default() += 1;
+        *last_sent.entry(A8MiniSimpleCommand::ZoomOut).or_default() += 1;
+        assert_eq!(last_sent[&A8MiniSimpleCommand::ZoomIn], 2);
+        assert_eq!(last_sent.len(), 2);
+
+        let commands: HashSet<A8MiniCommand> = [
+            A8MiniSimpleCommand::AutoCenter.into(),
+            A8MiniComplexCommand::SetYawPitchAngle(100, -50).into(),
+            A8MiniComplexCommand::SetYawPitchAngle(100, -50).into(),
+            A8MiniComplexCommand::SetTimeUTC(UNIX_EPOCH).into(),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(commands.len(), 3);
+
+        let queries: HashSet<A8MiniComplexHTTPQuery> =
+            [A8MiniComplexHTTPQuery::GetMedia(MediaKind::Photo, 1), A8MiniComplexHTTPQuery::GetMedia(MediaKind::Photo, 1)]
+                .into_iter()
+                .collect();
+        assert_eq!(queries.len(), 1);
+        assert!(HashSet::from([CameraModel::A8Mini, CameraModel::Unknown("99".to_string())]).contains(&CameraModel::A8Mini));
+    }
+
+    #[test]
+    fn test_gimbal_limits_by_model() {
+        let a8 = GimbalLimits::for_model(&CameraModel::A8Mini);
+        let zt30 = GimbalLimits::for_model(&CameraModel::Zt30);
+        assert_eq!(a8, GimbalLimits::A8_MINI);
+        assert_eq!(zt30, GimbalLimits::ZT30);
+        assert_eq!(GimbalLimits::for_model(&CameraModel::Unknown("99".to_string())), GimbalLimits::A8_MINI);
+
+        assert!(a8.check(135.0, -90.0).is_ok());
+        assert!(matches!(a8.check(200.0, 0.0), Err(A8MiniError::OutOfRange { name: "yaw_deg", .. })));
+        assert!(zt30.check(200.0, 0.0).is_ok());
+        assert_eq!(a8.clamp(200.0, -100.0), (135.0, -90.0));
+        assert_eq!(zt30.clamp(200.0, 10.0), (200.0, 10.0));
+        assert_eq!(zt30.commandable(), a8);
+        assert_eq!(GimbalLimits::A2_MINI.commandable(), GimbalLimits::A2_MINI);
+        assert!(matches!(zt30.check(0.0, 30.0), Err(A8MiniError::OutOfRange { name: "pitch_deg", .. })));
+
+        assert_eq!(
+            A8MiniComplexCommand::yaw_pitch_angle_deg_within(-135.0, 25.0, &zt30),
+            Ok(A8MiniComplexCommand::SetYawPitchAngle(-1350, 250))
+        );
+        assert!(matches!(
+            A8MiniComplexCommand::yaw_pitch_angle_deg_within(-270.0, 25.0, &zt30),
+            Err(A8MiniError::OutOfRange { name: "yaw_deg", max: 135.0, .. })
+        ));
+        assert_eq!(
+            A8MiniComplexCommand::SetYawPitchAngle(-2700, 250).to_bytes()[8..12],
+            A8MiniComplexCommand::SetYawPitchAngle(-1350, 250).to_bytes()[8..12]
+        );
+    }
+
+    #[test]
+    fn test_yaw_pitch_speed_normalized() {
+        let speed = A8MiniComplexCommand::yaw_pitch_speed_normalized;
+        // stick noise within the deadband is ignored
+        assert_eq!(speed(0.05, -0.1, 0.1), A8MiniComplexCommand::SetYawPitchSpeed(0, 0));
+        // travel past the deadband is rescaled from 0
+        assert_eq!(speed(0.55, -0.325, 0.1), A8MiniComplexCommand::SetYawPitchSpeed(50, -25));
+        assert_eq!(speed(0.5, -1.0, 0.0), A8MiniComplexCommand::SetYawPitchSpeed(50, -100));
+        // saturation
+        assert_eq!(speed(3.0, -7.5, 0.1), A8MiniComplexCommand::SetYawPitchSpeed(100, -100));
+        assert_eq!(speed(f32::NAN, 1.0, 1.0), A8MiniComplexCommand::SetYawPitchSpeed(0, 0));
+    }
+
+    #[test]
+    fn test_yaw_pitch_angle_deg_boundaries() {
+        assert_eq!(
+            A8MiniComplexCommand::yaw_pitch_angle_deg(135.0, 25.0),
+            Ok(A8MiniComplexCommand::SetYawPitchAngle(1350, 250))
+        );
+        assert_eq!(
+            A8MiniComplexCommand::yaw_pitch_angle_deg(-135.0, -90.0),
+            Ok(A8MiniComplexCommand::SetYawPitchAngle(-1350, -900))
+        );
+        assert_eq!(
+            A8MiniComplexCommand::yaw_pitch_angle_deg(12.34, -0.06),
+            Ok(A8MiniComplexCommand::SetYawPitchAngle(123, -1))
+        );
+
+        assert!(matches!(
+            A8MiniComplexCommand::yaw_pitch_angle_deg(135.1, 0.0),
+            Err(A8MiniError::OutOfRange { name: "yaw_deg", .. })
+        ));
+        assert!(matches!(
+            A8MiniComplexCommand::yaw_pitch_angle_deg(-135.1, 0.0),
+            Err(A8MiniError::OutOfRange { name: "yaw_deg", .. })
+        ));
+        assert!(matches!(
+            A8MiniComplexCommand::yaw_pitch_angle_deg(0.0, 25.1),
+            Err(A8MiniError::OutOfRange { name: "pitch_deg", .. })
+        ));
+        assert!(matches!(
+            A8MiniComplexCommand::yaw_pitch_angle_deg(0.0, -90.1),
+            Err(A8MiniError::OutOfRange { name: "pitch_deg", .. })
+        ));
+        assert!(A8MiniComplexCommand::yaw_pitch_angle_deg(f32::NAN, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_attitude_from_extended_payload() {
+        let mut payload = vec![0xc7, 0x01, 0xd4, 0xfe, 0x0c, 0x00, 0xfb, 0xff, 0x00, 0x00, 0x01, 0x00];
+        payload.extend_from_slice(&[0xaa, 0xbb, 0xcc, 0xdd]);
+        let frame = AckFrame::parse(&build_frame(0x0D, &payload)).unwrap();
+        assert_eq!(frame.payload.len(), 16);
+
+        let attitude = A8MiniAttitude::from_payload(&frame.payload).unwrap();
+        assert_eq!(
+            attitude,
+            A8MiniAttitude { theta_yaw: 455, theta_pitch: -300, theta_roll: 12, v_yaw: -5, v_pitch: 0, v_roll: 1 }
+        );
+        assert_eq!(
+            A8MiniAttitude::from_payload(&payload[..11]),
+            Err(A8MiniError::FrameTooShort { expected: 12, actual: 11 })
+        );
+    }
+
+    #[test]
+    fn test_attitude_from_known_orientation_packet() {
+        // yaw -90°, pitch -45° (tilted down), roll +3°; pitching at 0.5°/s, rolling at -0.2°/s
+        let packet = [
+            0x55, 0x66, 0x02, 0x0c, 0x00, 0x2a, 0x00, 0x0d, 0x7c, 0xfc, 0x3e, 0xfe, 0x1e, 0x00, 0x00, 0x00, 0x05,
+            0x00, 0xfe, 0xff, 0xda, 0x69,
+        ];
+        let frame = AckFrame::parse(&packet).unwrap();
+        let attitude = A8MiniAttitude::from_payload(&frame.payload).unwrap();
+
+        assert_eq!((attitude.yaw_deg(), attitude.pitch_deg(), attitude.roll_deg()), (-90.0, -45.0, 3.0));
+        assert_eq!(attitude.angular_velocity_deg(), [0.0, 0.5, -0.2]);
+        // the serde layout, used by bincode, agrees with the manual decode
+        assert_eq!(bincode::deserialize::<A8MiniAttitude>(&frame.payload).unwrap(), attitude);
+    }
+
+    #[test]
+    fn test_camera_model_from_hardware_id() {
+        assert_eq!(CameraModel::from_hardware_id(b"7302123456\0\0"), CameraModel::A8Mini);
+        assert_eq!(CameraModel::from_hardware_id(b"7A01"), CameraModel::Zt30);
+        assert_eq!(CameraModel::from_hardware_id(b"9901"), CameraModel::Unknown("99".to_string()));
+        assert_eq!(CameraModel::from_hardware_id(b"9"), CameraModel::Unknown("9".to_string()));
+    }
+
+    #[test]
+    fn test_ack_payload_from_frame() {
+        let decode = |command_id: u8, payload: &[u8]| {
+            let frame = AckFrame::parse(&build_frame(command_id, payload)).unwrap();
+            AckPayload::from_frame(&frame)
+        };
+
+        assert_eq!(
+            decode(0x01, &[0x01, 0x02, 0x03, 0x6e, 0x04, 0x04, 0x00, 0x73, 0x00, 0x00, 0x00, 0x00]),
+            Ok(AckPayload::Firmware(A8MiniFirmwareVersion {
+                code_ver_byte0: 0x01,
+                code_ver_byte1: 0x02,
+                code_ver_byte2: 0x03,
+                code_ver_byte3: 0x6e,
+                gimbal_ver_byte0: 0x04,
+                gimbal_ver_byte1: 0x04,
+                gimbal_ver_byte2: 0x00,
+                gimbal_ver_byte3: 0x73,
+            }))
+        );
+        assert_eq!(decode(0x02, b"7302A800\0\0\0\0"), Ok(AckPayload::HardwareId("7302A800".to_string())));
+        assert!(matches!(decode(0x0A, &[0, 1, 0, 0, 1, 1, 0]), Ok(AckPayload::GimbalStatus(GimbalStatus { hdr: 1, .. }))));
+        assert!(matches!(
+            decode(0x0D, &[0x2c, 0x01, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+            Ok(AckPayload::Attitude(A8MiniAttitude { theta_yaw: 300, .. }))
+        ));
+        assert_eq!(decode(0x0E, &[0x2c, 0x01, 0x9c, 0xff, 0, 0]), Ok(AckPayload::Angles(300, -100, 0)));
+        assert_eq!(decode(0x15, &[0xe8, 0x03]), Ok(AckPayload::LaserRange(1000)));
+        assert_eq!(decode(0x16, &[6, 0]), Ok(AckPayload::MaxZoom(6.0)));
+        assert_eq!(decode(0x18, &[2, 5]), Ok(AckPayload::Zoom(2.5)));
+        assert!(matches!(
+            decode(0x20, &[1, 2, 0x80, 0x07, 0x38, 0x04, 0xa0, 0x0f, 30]),
+            Ok(AckPayload::CodecSpecs(CodecSpecs { width: 1920, height: 1080, .. }))
+        ));
+        assert_eq!(decode(0x21, &[1, 1]), Ok(AckPayload::Status(true)));
+        assert_eq!(decode(0x08, &[0]), Ok(AckPayload::Status(false)));
+        assert_eq!(decode(0x99, &[7]), Ok(AckPayload::Raw { command_id: 0x99, payload: vec![7] }));
+
+        assert_eq!(decode(0x0E, &[0; 4]), Err(A8MiniError::FrameTooShort { expected: 6, actual: 4 }));
+        assert_eq!(decode(0x30, &[]), Err(A8MiniError::FrameTooShort { expected: 1, actual: 0 }));
+        assert_eq!(decode(0x20, &[0; 8]), Err(A8MiniError::FrameTooShort { expected: 9, actual: 8 }));
+    }
+
+    #[test]
+    fn test_write_to_matches_to_bytes() {
+        let mut buf = [0u8; constants::MAX_FRAME_LEN];
+        let commands = A8MiniSimpleCommand::all().iter().copied().map(A8MiniCommand::from).chain(
+            [
+                A8MiniComplexCommand::SetYawPitchAngle(-900, 250),
+                A8MiniComplexCommand::SetTimeUTC(UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+                A8MiniComplexCommand::SetCodecSpecs(1, 2, 1920, 1080, 4000, 0),
+                A8MiniComplexCommand::GetZoomLevel,
+            ]
+            .map(A8MiniCommand::from),
+        );
+        for command in commands {
+            let len = command.write_to(&mut buf).unwrap();
+            assert_eq!(buf[..len], command.to_bytes(), "{:?}", command);
+        }
+
+        assert_eq!(
+            A8MiniSimpleCommand::AutoCenter.write_to(&mut buf[..10]),
+            Err(A8MiniError::BufferTooSmall { needed: 11, actual: 10 })
+        );
+        assert_eq!(
+            A8MiniComplexCommand::GetCodecSpecs(0).write_to(&mut [0u8; 4]),
+            Err(A8MiniError::BufferTooSmall { needed: 11, actual: 4 })
+        );
+    }
+
+    #[test]
+    fn test_absolute_zoom() {
+        let command = A8MiniComplexCommand::absolute_zoom(4.5, 6.0).unwrap();
+        assert_eq!(command, A8MiniComplexCommand::SetAbsoluteZoom(4, 5));
+        // same frame as the hardcoded Absolute Zoom (4.5x), which carries SEQ 1
+        let mut frame = command.to_bytes();
+        stamp_sequence(&mut frame, 1).unwrap();
+        assert_eq!(frame, [0x55, 0x66, 0x01, 0x02, 0x00, 0x01, 0x00, 0x0F, 0x04, 0x05, 0x60, 0xBB]);
+        #[allow(deprecated)]
+        let zoom_max = A8MiniSimpleCommand::ZoomMax;
+        assert_eq!(zoom_max.to_bytes(), command.to_bytes());
+        assert_eq!(A8MiniComplexCommand::absolute_zoom(1.04, 6.0), Ok(A8MiniComplexCommand::SetAbsoluteZoom(1, 0)));
+        assert!(A8MiniComplexCommand::absolute_zoom(0.5, 6.0).is_err());
+        assert_eq!(
+            A8MiniComplexCommand::absolute_zoom(6.5, 6.0),
+            Err(A8MiniError::OutOfRange { name: "zoom", value: 6.5, min: 1.0, max: 6.0 })
+        );
+        assert_eq!(zoom_from_payload(&[2, 5]), Ok(2.5));
+        assert!(zoom_from_payload(&[2]).is_err());
+    }
+
+    #[test]
+    fn test_model_specific_commands() {
+        let hdmi = A8MiniSimpleCommand::SetVideoOutputHDMI;
+        let laser = A8MiniSimpleCommand::LaserRangefinderInformation;
+        assert!(hdmi.supported_by(&CameraModel::A8Mini));
+        assert!(!hdmi.supported_by(&CameraModel::Zt30));
+        assert!(laser.supported_by(&CameraModel::Zt30));
+        assert!(!A8MiniCommand::from(laser).supported_by(&CameraModel::A8Mini));
+        assert!(laser.supported_by(&CameraModel::Unknown("99".to_string())));
+        assert!(A8MiniSimpleCommand::AutoCenter.supported_by(&CameraModel::Zr10));
+    }
+
+    #[test]
+    fn test_gimbal_status_from_payload() {
+        let status = GimbalStatus::from_payload(&[0x00, 0x01, 0x00, 0x01, 0x02, 0x01, 0x00, 0x00]).unwrap();
+        assert_eq!(
+            status,
+            GimbalStatus { hdr: 1, record_status: 1, motion_mode: 2, mounting_direction: 1, video_output: 0 }
+        );
+        assert_eq!(status.mode(), Some(GimbalMode::Fpv));
+        assert!(status.sd_card_present());
+        assert!(!GimbalStatus { record_status: 2, ..status }.sd_card_present());
+        assert_eq!(
+            GimbalStatus::from_payload(&[0x00; 6]),
+            Err(A8MiniError::FrameTooShort { expected: 7, actual: 6 })
+        );
+    }
+
+    #[test]
+    fn test_all_simple_commands() {
+        assert_eq!(A8MiniSimpleCommand::all().len(), 34);
+        assert_eq!(A8MiniSimpleCommand::all().len(), constants::HARDCODED_COMMANDS.len());
+        for command in A8MiniSimpleCommand::all() {
+            assert_eq!(command.name(), format!("{:?}", command));
+        }
+    }
+
+    #[test]
+    fn test_trailing_simple_commands_map_to_their_frames() {
+        let expected = [
+            (A8MiniSimpleCommand::RebootCamera, 0x80, vec![0x01, 0x00]),
+            (A8MiniSimpleCommand::RebootGimbal, 0x80, vec![0x00, 0x01]),
+            (A8MiniSimpleCommand::Resolution4k, 0x21, vec![0x00, 0x02, 0x00, 0x0f, 0x70, 0x08, 0x98, 0x3a, 0x00]),
+            (A8MiniSimpleCommand::Heartbeat, 0x00, vec![0x00]),
+            (A8MiniSimpleCommand::GimbalStatus, 0x0a, vec![]),
+            (A8MiniSimpleCommand::PointDown, 0x08, vec![0x04]),
+            (A8MiniSimpleCommand::PointForward, 0x08, vec![0x03]),
+        ];
+        for (command, command_id, payload) in expected {
+            let frame = AckFrame::parse(&command.to_bytes()).unwrap();
+            assert_eq!(frame.command_id, command_id, "{}", command);
+            assert_eq!(frame.payload, payload, "{}", command);
+        }
+    }
+
+    #[test]
+    fn test_point_presets_frames() {
+        assert_eq!(
+            A8MiniSimpleCommand::PointDown.to_bytes(),
+            [0x55, 0x66, 0x01, 0x01, 0x00, 0x00, 0x00, 0x08, 0x04, 0x74, 0x42]
+        );
+        assert_eq!(
+            A8MiniSimpleCommand::PointForward.to_bytes(),
+            [0x55, 0x66, 0x01, 0x01, 0x00, 0x00, 0x00, 0x08, 0x03, 0x93, 0x32]
+        );
+        assert_eq!(A8MiniSimpleCommand::PointDown.to_bytes(), build_frame(0x08, &[0x04]));
+        assert_eq!(A8MiniSimpleCommand::PointForward.to_bytes(), build_frame(0x08, &[0x03]));
+    }
+
+    #[test]
+    fn test_simple_command_names_round_trip() {
+        for (index, command) in A8MiniSimpleCommand::ALL.into_iter().enumerate() {
+            assert_eq!(command as usize, index);
+            assert_eq!(command.to_string().parse::<A8MiniSimpleCommand>(), Ok(command));
+        }
+        assert_eq!(
+            "Autocenter".parse::<A8MiniSimpleCommand>(),
+            Err(A8MiniError::UnknownCommand("Autocenter".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_complex_command_names_round_trip() {
+        let commands = [
+            A8MiniComplexCommand::SetYawPitchSpeed(-100, 45),
+            A8MiniComplexCommand::SetYawPitchAngle(1350, -900),
+            A8MiniComplexCommand::SetTimeUTC(UNIX_EPOCH + Duration::from_micros(1_700_000_000_000_042)),
+            A8MiniComplexCommand::GetCodecSpecs(2),
+            A8MiniComplexCommand::SetCodecSpecs(1, 2, 1920, 1080, 4000, 0),
+            A8MiniComplexCommand::RequestGimbalDataStream(1, 5),
+            A8MiniComplexCommand::SetAbsoluteZoom(4, 5),
+            A8MiniComplexCommand::GetZoomLevel,
+        ];
+        for command in commands {
+            assert_eq!(command.to_string().parse::<A8MiniComplexCommand>(), Ok(command));
+        }
+
+        assert_eq!(
+            "SetYawPitchSpeed 10 -5".parse::<A8MiniComplexCommand>(),
+            Ok(A8MiniComplexCommand::SetYawPitchSpeed(10, -5))
+        );
+        assert_eq!(
+            "SetTimeUTC 1700000000.5".parse::<A8MiniComplexCommand>(),
+            Ok(A8MiniComplexCommand::SetTimeUTC(UNIX_EPOCH + Duration::from_millis(1_700_000_000_500)))
+        );
+        assert_eq!(
+            "SetYawPitchSpeed 10 300".parse::<A8MiniComplexCommand>(),
+            Err(A8MiniError::InvalidArguments { command: "SetYawPitchSpeed", expected: "<yaw i8> <pitch i8>" })
+        );
+        assert!("GetCodecSpecs".parse::<A8MiniComplexCommand>().is_err());
+        assert!("Spin 1 2".parse::<A8MiniComplexCommand>().is_err());
+    }
+
+    #[test]
+    fn test_http_query_names_round_trip() {
+        let queries = [
+            A8MiniComplexHTTPQuery::GetDirectories(MediaKind::Video),
+            A8MiniComplexHTTPQuery::GetMediaCount(MediaKind::Photo),
+            A8MiniComplexHTTPQuery::GetMedia(MediaKind::Video, 3),
+            A8MiniComplexHTTPQuery::GetMediaList { kind: MediaKind::Photo, start: 5, count: 10 },
+            A8MiniComplexHTTPQuery::GetPhotoByName("IMG 0001 (copy).jpg".to_string()),
+            A8MiniComplexHTTPQuery::GetVideoByName("REC_0001.mp4".to_string()),
+        ];
+        for query in queries {
+            assert_eq!(query.to_string().parse::<A8MiniComplexHTTPQuery>(), Ok(query));
+        }
+
+        assert_eq!(
+            A8MiniComplexHTTPQuery::GetMedia(MediaKind::Video, 3).to_string(),
+            "GetMedia 1 3"
+        );
+        assert_eq!(
+            A8MiniComplexHTTPQuery::GetMediaCount(MediaKind::Photo).url(),
+            format!(
+                "http://{}:{}{}",
+                constants::CAMERA_IP,
+                constants::CAMERA_HTTP_PORT,
+                A8MiniComplexHTTPQuery::GetMediaCount(MediaKind::Photo).path()
+            )
+        );
+        assert_eq!(
+            "GetMedia 2 3".parse::<A8MiniComplexHTTPQuery>(),
+            Err(A8MiniError::InvalidArguments { command: "GetMedia", expected: "<kind 0|1> <index u32>" })
+        );
+        assert!("GetPhotoByName".parse::<A8MiniComplexHTTPQuery>().is_err());
+        assert_eq!(
+            "GetPhotos 0".parse::<A8MiniComplexHTTPQuery>(),
+            Err(A8MiniError::UnknownCommand("GetPhotos".to_string()))
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_http_queries_display_as_replacements() {
+        assert_eq!(A8MiniSimpleHTTPQuery::GetMediaCountVideos.to_string(), "GetMediaCount 1");
+        assert_eq!(A8MiniComplexHTTPQuery::GetPhoto(7).to_string(), "GetMedia 0 7");
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_media_kind_queries_match_deprecated_aliases() {
+        type Query = dyn HTTPQuery<Response = HTTPResponseData>;
+        let pairs: [(&Query, &Query); 6] = [
+            (&A8MiniComplexHTTPQuery::GetDirectories(MediaKind::Photo), &A8MiniSimpleHTTPQuery::GetDirectoriesPhotos),
+            (&A8MiniComplexHTTPQuery::GetDirectories(MediaKind::Video), &A8MiniSimpleHTTPQuery::GetDirectoriesVideos),
+            (&A8MiniComplexHTTPQuery::GetMediaCount(MediaKind::Photo), &A8MiniSimpleHTTPQuery::GetMediaCountPhotos),
+            (&A8MiniComplexHTTPQuery::GetMediaCount(MediaKind::Video), &A8MiniSimpleHTTPQuery::GetMediaCountVideos),
+            (&A8MiniComplexHTTPQuery::GetMedia(MediaKind::Photo, 7), &A8MiniComplexHTTPQuery::GetPhoto(7)),
+            (&A8MiniComplexHTTPQuery::GetMedia(MediaKind::Video, 12), &A8MiniComplexHTTPQuery::GetVideo(12)),
+        ];
+        for (query, alias) in pairs {
+            assert_eq!(query.path(), alias.path());
+        }
+        assert_eq!(
+            A8MiniComplexHTTPQuery::GetMedia(MediaKind::Photo, 7).path(),
+            "/photo/101SIYI_IMG/IMG_0007.jpg"
+        );
+        assert_eq!(
+            A8MiniComplexHTTPQuery::GetMedia(MediaKind::Video, 12).path(),
+            "/photo/100SIYI_VID/REC_0012.mp4"
+        );
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let attitude = A8MiniAttitude {
+            theta_yaw: 455,
+            theta_pitch: -300,
+            theta_roll: 12,
+            v_yaw: -5,
+            v_pitch: 0,
+            v_roll: 1,
+        };
+        let json = serde_json::to_string(&attitude).unwrap();
+        assert_eq!(serde_json::from_str::<A8MiniAttitude>(&json).unwrap(), attitude);
+
+        let specs = CodecSpecs {
+            stream_type: 1,
+            codec: 2,
+            width: 1920,
+            height: 1080,
+            bitrate_kbps: 4000,
+            framerate: 30,
+        };
+        let json = serde_json::to_string(&specs).unwrap();
+        assert_eq!(serde_json::from_str::<CodecSpecs>(&json).unwrap(), specs);
+
+        let firmware: A8MiniFirmwareVersion =
+            bincode::deserialize(&[0x01, 0x02, 0x03, 0x6e, 0x04, 0x04, 0x00, 0x73]).unwrap();
+        let json = serde_json::to_string(&firmware).unwrap();
+        assert_eq!(serde_json::from_str::<A8MiniFirmwareVersion>(&json).unwrap(), firmware);
+    }
+
+    #[test]
+    fn test_attitude_scaling() {
+        let attitude = A8MiniAttitude {
+            theta_yaw: -1350,
+            theta_pitch: 250,
+            theta_roll: -5,
+            v_yaw: 123,
+            v_pitch: -10,
+            v_roll: 0,
+        };
+
+        assert_eq!(attitude.yaw_deg(), -135.0);
+        assert_eq!(attitude.pitch_deg(), 25.0);
+        assert_eq!(attitude.roll_deg(), -0.5);
+        assert_eq!(attitude.angular_velocity_deg(), [12.3, -1.0, 0.0]);
+        assert_eq!(
+            attitude.to_string(),
+            "GIMBAL ATTITUDE:\n\tYaw:   -135.0°\n\tPitch: 25.0°\n\tRoll:  -0.5°\n\t(Speeds: Y=12.3°/s, P=-1.0°/s, R=0.0°/s)"
+        );
+    }
+
+    #[test]
+    fn test_attitude_quaternion() {
+        let approx_eq = |a: [f32; 4], b: [f32; 4]| a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-5);
+        let attitude = |yaw: i16, pitch: i16, roll: i16| A8MiniAttitude {
+            theta_yaw: yaw,
+            theta_pitch: pitch,
+            theta_roll: roll,
+            v_yaw: 0,
+            v_pitch: 0,
+            v_roll: 0,
+        };
+        let half = std::f32::consts::FRAC_1_SQRT_2;
+
+        assert!(approx_eq(attitude(0, 0, 0).to_quaternion(), [1.0, 0.0, 0.0, 0.0]));
+        assert!(approx_eq(attitude(900, 0, 0).to_quaternion(), [half, 0.0, 0.0, half]));
+        assert!(approx_eq(attitude(0, -900, 0).to_quaternion(), [half, 0.0, -half, 0.0]));
+        assert!(approx_eq(attitude(0, 0, 900).to_quaternion(), [half, half, 0.0, 0.0]));
+        // yaw 90 then pitch 90: [0.5, -0.5, 0.5, 0.5]
+        assert!(approx_eq(attitude(900, 900, 0).to_quaternion(), [0.5, -0.5, 0.5, 0.5]));
+    }
+
+    #[test]
+    fn test_codec_spec_commands() {
+        assert_eq!(
+            A8MiniComplexCommand::GetCodecSpecs(0).to_bytes(),
+            [0x55, 0x66, 0x01, 0x01, 0x00, 0x00, 0x00, 0x20, 0x00, 0xbf, 0x8d]
+        );
+
+        // identical to the hardcoded 4k resolution frame
+        assert_eq!(
+            A8MiniComplexCommand::SetCodecSpecs(0, 2, 3840, 2160, 15000, 0).to_bytes(),
+            A8MiniSimpleCommand::Resolution4k.to_bytes()
+        );
+    }
+
+    #[test]
+    fn test_codec_specs_deserialization() {
+        let payload: &[u8] = &[0x01, 0x02, 0x80, 0x07, 0x38, 0x04, 0xb8, 0x0b, 0x1e];
+        let specs: CodecSpecs = bincode::deserialize(payload).unwrap();
+
+        assert_eq!(
+            specs,
+            CodecSpecs {
+                stream_type: 1,
+                codec: 2,
+                width: 1920,
+                height: 1080,
+                bitrate_kbps: 3000,
+                framerate: 30,
+            }
+        );
+    }
+
+    #[test]
+    fn test_video_codec_bytes() {
+        assert_eq!(VideoCodec::H264 as u8, 1);
+        assert_eq!(VideoCodec::H265 as u8, 2);
+        assert_eq!(VideoCodec::from_byte(2), Some(VideoCodec::H265));
+        assert_eq!(VideoCodec::from_byte(3), None);
+
+        // codec is the second payload byte of SetCodecSpecs
+        let command = CodecConfig::fhd_1080p(4000).codec(VideoCodec::H265).to_command().unwrap();
+        assert_eq!(command.to_bytes()[9], 0x02);
+    }
+
+    #[test]
+    fn test_resolution_codec_config() {
+        // the 4k config matches the hardcoded Resolution4k frame
+        let uhd = CodecConfig::fhd_1080p(15000).resolution(Resolution::Uhd4k).to_command().unwrap();
+        assert_eq!(uhd.to_bytes(), A8MiniSimpleCommand::Resolution4k.to_bytes());
+
+        let fhd = CodecConfig::uhd_4k(15000).resolution(Resolution::Hd1080);
+        assert_eq!(fhd, CodecConfig::fhd_1080p(15000));
+        assert_eq!(Resolution::from_dimensions(1920, 1080), Some(Resolution::Hd1080));
+        assert_eq!(Resolution::from_dimensions(640, 480), None);
+    }
+
+    #[test]
+    fn test_codec_config_validation() {
+        assert_eq!(
+            CodecConfig::uhd_4k(15000).to_command(),
+            Ok(A8MiniComplexCommand::SetCodecSpecs(0, 2, 3840, 2160, 15000, 0))
+        );
+        assert_eq!(
+            CodecConfig::hd_720p(1000).stream_type(2).codec(VideoCodec::H264).to_command(),
+            Ok(A8MiniComplexCommand::SetCodecSpecs(2, 1, 1280, 720, 1000, 0))
+        );
+        assert!(CodecConfig::fhd_1080p(20000).validate().is_ok());
+
+        let unsupported = [
+            CodecConfig::fhd_1080p(999),
+            CodecConfig::fhd_1080p(20001),
+            CodecConfig::fhd_1080p(4000).stream_type(3),
+            CodecConfig { width: 1920, height: 720, ..CodecConfig::fhd_1080p(4000) },
+        ];
+        for config in unsupported {
+            assert!(
+                matches!(config.to_command(), Err(A8MiniError::UnsupportedCodecConfig { .. })),
+                "{:?}",
+                config
+            );
+        }
+
+        for (stream_type, config) in CodecConfig::factory_defaults().into_iter().enumerate() {
+            assert_eq!(config.stream_type as usize, stream_type);
+            assert!(config.validate().is_ok());
+        }
+        let recording = CodecConfig::factory_defaults()[0].to_command().unwrap();
+        assert_eq!(recording.to_bytes(), A8MiniSimpleCommand::Resolution4k.to_bytes());
+
+        let message = CodecConfig::uhd_4k(50000).validate().unwrap_err().to_string();
+        assert!(message.contains("3840x2160, 1920x1080 or 1280x720"), "{}", message);
+    }
+
+    #[test]
+    fn test_set_time_utc_command() {
+        // 2024-01-01T00:00:00.123456Z
+        let time = UNIX_EPOCH + std::time::Duration::from_micros(1_704_067_200_123_456);
+        let expected_command: [u8; 18] = [
+            0x55, 0x66, 0x01, 0x08, 0x00, 0x00, 0x00, 0x30, 0x40, 0x02, 0x23, 0x10, 0xd7, 0x0d,
+            0x06, 0x00, 0xc3, 0x1a,
+        ];
+        assert_eq!(A8MiniComplexCommand::SetTimeUTC(time).to_bytes(), expected_command);
+
+        let before_epoch = UNIX_EPOCH - std::time::Duration::from_secs(1);
+        assert_eq!(
+            A8MiniComplexCommand::SetTimeUTC(before_epoch).to_bytes()[8..16],
+            [0; 8]
+        );
+    }
+}
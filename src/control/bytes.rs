@@ -0,0 +1,52 @@
+//! Multi-byte field readers for frame decoders, so the byte order of every field is spelled out.
+//!
+//! Like slice indexing, each reader panics if `bytes` ends before `offset + 2`; decoders check
+//! the payload length first.
+
+/// Reads a little-endian (low byte first) `u16` at `offset`.
+pub fn read_u16_le(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+/// Reads a little-endian (low byte first) `i16` at `offset`.
+pub fn read_i16_le(bytes: &[u8], offset: usize) -> i16 {
+    i16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+/// Reads a big-endian (high byte first) `i16` at `offset`.
+pub fn read_i16_be(bytes: &[u8], offset: usize) -> i16 {
+    i16::from_be_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_u16_le() {
+        assert_eq!(read_u16_le(&[0x34, 0x12], 0), 0x1234);
+        assert_eq!(read_u16_le(&[0x00, 0xff, 0xff], 1), 0xffff);
+    }
+
+    #[test]
+    fn test_read_i16_le() {
+        assert_eq!(read_i16_le(&[0x46, 0x05], 0), 1350);
+        // -135.0° yaw, in tenths
+        assert_eq!(read_i16_le(&[0xba, 0xfa], 0), -1350);
+        assert_eq!(read_i16_le(&[0x00, 0xff, 0xff], 1), -1);
+        assert_eq!(read_i16_le(&[0x00, 0x80], 0), i16::MIN);
+    }
+
+    #[test]
+    fn test_read_i16_be() {
+        assert_eq!(read_i16_be(&[0x05, 0x46], 0), 1350);
+        assert_eq!(read_i16_be(&[0xfa, 0xba], 0), -1350);
+        assert_eq!(read_i16_be(&[0x00, 0x80, 0x00], 1), i16::MIN);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_read_past_end_panics() {
+        read_u16_le(&[0x01, 0x02], 1);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::control::{CameraModel, MediaKind};
+use std::fmt;
+
+/// Errors produced while talking to or decoding data from the A8 mini.
+#[derive(Debug, Clone, PartialEq)]
+pub enum A8MiniError {
+    /// Frame did not start with the `0x55 0x66` STX mark.
+    InvalidHeader([u8; 2]),
+    /// Frame is shorter than its header or declared data length requires.
+    FrameTooShort { expected: usize, actual: usize },
+    /// CRC16 trailer does not match the frame contents.
+    CrcMismatch { expected: u16, actual: u16 },
+    /// Argument is outside the range the camera accepts.
+    OutOfRange { name: &'static str, value: f32, min: f32, max: f32 },
+    /// Stream/codec/resolution/bitrate combination the camera doesn't support.
+    UnsupportedCodecConfig { stream_type: u8, codec: u8, width: u16, height: u16, bitrate_kbps: u16 },
+    /// The camera didn't reach the awaited state in time. Names what was awaited.
+    Timeout(&'static str),
+    /// Camera response lacks a field it should always carry. Names the field.
+    #[deprecated(note = "no longer produced: responses missing a field fail with `MalformedResponse`")]
+    MissingField(&'static str),
+    /// Buffer passed to `Command::write_to` can't hold the frame.
+    BufferTooSmall { needed: usize, actual: usize },
+    /// The socket sent only part of a frame.
+    PartialSend { sent: usize, expected: usize },
+    /// The connected camera model doesn't implement this command.
+    UnsupportedByModel { command: String, model: CameraModel },
+    /// Command name that doesn't match any command.
+    UnknownCommand(String),
+    /// Command arguments are missing, extra or unparseable. Names the expected arguments.
+    InvalidArguments { command: &'static str, expected: &'static str },
+    /// HTTP API answered with something other than the expected JSON, e.g. an HTML error page.
+    /// Keeps the start of the body.
+    MalformedResponse { url: String, body_snippet: String },
+    /// `ffmpeg` couldn't grab a frame from the RTSP stream. Keeps the end of its error output.
+    FrameCapture(String),
+    /// `A8MiniBuilder::require_limits` is set and the gimbal model couldn't be determined, so
+    /// its limits aren't known.
+    ModelUnknown,
+    /// The camera ACKed a command with a failure status instead of executing it. Keeps the
+    /// command ID and the status byte (SIYI only documents 1 as success).
+    CameraNack { command_id: u8, code: u8 },
+    /// The caller cancelled the operation, e.g. with `A8Mini::download_media_cancellable`.
+    Cancelled,
+    /// The SD card holds no files of this kind.
+    NoMedia(MediaKind),
+    /// Nothing answered the connection handshake at this address.
+    NoCameraFound(String),
+    /// No route to this address, e.g. when connected to the wrong network.
+    NetworkUnreachable(String),
+    /// Socket operation failed. Keeps the kind and message, as `std::io::Error` isn't `Clone`.
+    Io { kind: std::io::ErrorKind, message: String },
+}
+
+#[allow(deprecated)]
+impl fmt::Display for A8MiniError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            A8MiniError::InvalidHeader(stx) => {
+                write!(f, "invalid frame header {:#04x} {:#04x}, expected 0x55 0x66", stx[0], stx[1])
+            }
+            A8MiniError::FrameTooShort { expected, actual } => {
+                write!(f, "frame too short: expected {} bytes, got {}", expected, actual)
+            }
+            A8MiniError::CrcMismatch { expected, actual } => {
+                write!(f, "CRC mismatch: expected {:#06x}, got {:#06x}", expected, actual)
+            }
+            A8MiniError::OutOfRange { name, value, min, max } => {
+                write!(f, "{} = {} is out of range [{}, {}]", name, value, min, max)
+            }
+            A8MiniError::UnsupportedCodecConfig { stream_type, codec, width, height, bitrate_kbps } => {
+                write!(
+                    f,
+                    "unsupported codec config: stream {}, codec {}, {}x{} @ {} kbps \
+                     (supported: stream 0-2, codec 1 (H.264) or 2 (H.265), \
+                     3840x2160, 1920x1080 or 1280x720, 1000-20000 kbps)",
+                    stream_type, codec, width, height, bitrate_kbps
+                )
+            }
+            A8MiniError::Timeout(what) => write!(f, "timed out waiting for {}", what),
+            A8MiniError::MissingField(field) => write!(f, "response is missing field `{}`", field),
+            A8MiniError::BufferTooSmall { needed, actual } => {
+                write!(f, "buffer too small: frame needs {} bytes, buffer holds {}", needed, actual)
+            }
+            A8MiniError::PartialSend { sent, expected } => {
+                write!(f, "partial send: {} of {} frame bytes sent", sent, expected)
+            }
+            A8MiniError::UnsupportedByModel { command, model } => {
+                write!(f, "{} is not supported by the {:?}", command, model)
+            }
+            A8MiniError::UnknownCommand(name) => write!(f, "unknown command `{}`", name),
+            A8MiniError::InvalidArguments { command, expected } => {
+                write!(f, "invalid arguments for {}, expected {}", command, expected)
+            }
+            A8MiniError::MalformedResponse { url, body_snippet } => {
+                write!(f, "malformed response from {}: {:?}", url, body_snippet)
+            }
+            A8MiniError::FrameCapture(message) => write!(f, "RTSP frame capture failed: {}", message),
+            A8MiniError::ModelUnknown => {
+                write!(f, "gimbal model unknown, refusing to move without knowing its limits")
+            }
+            A8MiniError::CameraNack { command_id, code } => {
+                write!(f, "camera rejected command {:#04x} with status {}", command_id, code)
+            }
+            A8MiniError::Cancelled => write!(f, "operation cancelled"),
+            A8MiniError::NoMedia(kind) => write!(f, "no {:?} files on the SD card", kind),
+            A8MiniError::NoCameraFound(address) => write!(f, "no camera answered at {}", address),
+            A8MiniError::NetworkUnreachable(address) => {
+                write!(f, "network unreachable: no route to {}, check the link to the camera", address)
+            }
+            A8MiniError::Io { kind, message } => write!(f, "I/O error ({:?}): {}", kind, message),
+        }
+    }
+}
+
+impl From<std::io::Error> for A8MiniError {
+    fn from(err: std::io::Error) -> Self {
+        A8MiniError::Io { kind: err.kind(), message: err.to_string() }
+    }
+}
+
+impl std::error::Error for A8MiniError {}
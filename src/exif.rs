@@ -0,0 +1,144 @@
+//! Minimal EXIF reader for the capture time of photos downloaded from the camera.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+const TAG_DATE_TIME: u16 = 0x0132;
+const TAG_EXIF_IFD: u16 = 0x8769;
+const TAG_DATE_TIME_ORIGINAL: u16 = 0x9003;
+
+/// Reads the capture time from a JPEG's EXIF `DateTimeOriginal`, falling back to `DateTime`.
+/// Returns `None` if the image has no EXIF data or no parseable timestamp.
+///
+/// EXIF times carry no timezone; they are taken as UTC, which holds when the camera clock was
+/// set with `A8Mini::sync_time_now`.
+pub fn parse_jpeg_timestamp(bytes: &[u8]) -> Option<DateTime<Utc>> {
+    let tiff = find_exif(bytes)?;
+    let reader = TiffReader::new(tiff)?;
+    let ifd0 = reader.u32(4)? as usize;
+
+    let original = reader
+        .find_tag(ifd0, TAG_EXIF_IFD)
+        .and_then(|entry| reader.find_tag(reader.u32(entry + 8)? as usize, TAG_DATE_TIME_ORIGINAL));
+    let entry = original.or_else(|| reader.find_tag(ifd0, TAG_DATE_TIME))?;
+
+    let text = reader.ascii(entry)?;
+    let time = NaiveDateTime::parse_from_str(text.trim_end_matches('\0').trim(), "%Y:%m:%d %H:%M:%S").ok()?;
+    Some(time.and_utc())
+}
+
+/// Returns the TIFF block of the APP1 `Exif` segment.
+fn find_exif(bytes: &[u8]) -> Option<&[u8]> {
+    if bytes.get(..2)? != [0xFF, 0xD8] {
+        return None;
+    }
+
+    let mut offset = 2;
+    loop {
+        let marker = bytes.get(offset..offset + 2)?;
+        // start of scan or end of image: no metadata follows
+        if marker[0] != 0xFF || marker[1] == 0xDA || marker[1] == 0xD9 {
+            return None;
+        }
+        let length = u16::from_be_bytes(bytes.get(offset + 2..offset + 4)?.try_into().ok()?) as usize;
+        let segment = bytes.get(offset + 4..offset + 2 + length)?;
+        if marker[1] == 0xE1 && segment.starts_with(b"Exif\0\0") {
+            return Some(&segment[6..]);
+        }
+        offset += 2 + length;
+    }
+}
+
+/// Bounds-checked reads from a TIFF block in either byte order.
+struct TiffReader<'a> {
+    data: &'a [u8],
+    little_endian: bool,
+}
+
+impl<'a> TiffReader<'a> {
+    fn new(data: &'a [u8]) -> Option<Self> {
+        let little_endian = match data.get(..2)? {
+            b"II" => true,
+            b"MM" => false,
+            _ => return None,
+        };
+        Some(TiffReader { data, little_endian })
+    }
+
+    fn u16(&self, offset: usize) -> Option<u16> {
+        let bytes: [u8; 2] = self.data.get(offset..offset + 2)?.try_into().ok()?;
+        Some(if self.little_endian { u16::from_le_bytes(bytes) } else { u16::from_be_bytes(bytes) })
+    }
+
+    fn u32(&self, offset: usize) -> Option<u32> {
+        let bytes: [u8; 4] = self.data.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if self.little_endian { u32::from_le_bytes(bytes) } else { u32::from_be_bytes(bytes) })
+    }
+
+    /// Offset of the 12-byte entry for `tag` in the IFD at `ifd`.
+    fn find_tag(&self, ifd: usize, tag: u16) -> Option<usize> {
+        let count = self.u16(ifd)? as usize;
+        (0..count)
+            .map(|i| ifd + 2 + 12 * i)
+            .find(|&entry| self.u16(entry) == Some(tag))
+    }
+
+    /// Value of an ASCII entry, stored inline when it fits in 4 bytes.
+    fn ascii(&self, entry: usize) -> Option<&'a str> {
+        let length = self.u32(entry + 4)? as usize;
+        let start = if length <= 4 { entry + 8 } else { self.u32(entry + 8)? as usize };
+        std::str::from_utf8(self.data.get(start..start + length)?).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// Little-endian TIFF with IFD0 pointing at an EXIF IFD holding `DateTimeOriginal`.
+    fn tiff_with_date_time_original(date: &[u8; 20]) -> Vec<u8> {
+        let mut tiff = b"II\x2a\x00\x08\x00\x00\x00".to_vec();
+        // IFD0 at 8: one entry, ExifIFD pointer to 26
+        tiff.extend_from_slice(&[0x01, 0x00, 0x69, 0x87, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 26, 0, 0, 0]);
+        tiff.extend_from_slice(&[0, 0, 0, 0]);
+        // EXIF IFD at 26: one entry, DateTimeOriginal, 20 ASCII bytes at 44
+        tiff.extend_from_slice(&[0x01, 0x00, 0x03, 0x90, 0x02, 0x00, 20, 0, 0, 0, 44, 0, 0, 0]);
+        tiff.extend_from_slice(&[0, 0, 0, 0]);
+        tiff.extend_from_slice(date);
+        tiff
+    }
+
+    fn jpeg_with_app1(tiff: &[u8]) -> Vec<u8> {
+        let mut jpeg = vec![0xFF, 0xD8];
+        // an unrelated APP0 segment first
+        jpeg.extend_from_slice(&[0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00]);
+        jpeg.extend_from_slice(&[0xFF, 0xE1]);
+        jpeg.extend_from_slice(&((tiff.len() + 8) as u16).to_be_bytes());
+        jpeg.extend_from_slice(b"Exif\0\0");
+        jpeg.extend_from_slice(tiff);
+        jpeg.extend_from_slice(&[0xFF, 0xDA, 0x00, 0x02, 0xFF, 0xD9]);
+        jpeg
+    }
+
+    #[test]
+    fn test_parse_date_time_original() {
+        let jpeg = jpeg_with_app1(&tiff_with_date_time_original(b"2024:06:01 12:34:56\0"));
+        assert_eq!(
+            parse_jpeg_timestamp(&jpeg),
+            Some(Utc.with_ymd_and_hms(2024, 6, 1, 12, 34, 56).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_missing_or_broken_exif_is_none() {
+        assert_eq!(parse_jpeg_timestamp(&crate::test_support::fake_jpeg(1)), None);
+        assert_eq!(parse_jpeg_timestamp(b"not a jpeg"), None);
+        assert_eq!(parse_jpeg_timestamp(&jpeg_with_app1(b"II\x2a\x00\xff\xff\x00\x00")), None);
+
+        let mut jpeg = jpeg_with_app1(&tiff_with_date_time_original(b"0000:00:00 00:00:00\0"));
+        assert_eq!(parse_jpeg_timestamp(&jpeg), None);
+        // truncated mid-segment
+        jpeg.truncate(20);
+        assert_eq!(parse_jpeg_timestamp(&jpeg), None);
+    }
+}
@@ -1,34 +1,94 @@
 #![allow(non_snake_case)]
 
-use anyhow::anyhow;
-use bincode::deserialize;
-use tokio::{net::UdpSocket, time::timeout};
-use tracing::{debug, error, info};
-use tokio::sync::mpsc;
-
+use anyhow::{anyhow, Context};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::atomic::{AtomicU16, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use futures_util::stream::{self, Stream, StreamExt};
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt};
+use tokio::{net::UdpSocket, task::JoinHandle, time::timeout};
+use tracing::{debug, error, info, instrument, trace, warn};
+use tokio::sync::{mpsc, watch, Mutex};
+
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod builder;
 pub mod checksum;
 pub mod constants;
 pub mod control;
+pub mod error;
+pub mod exif;
+#[cfg(feature = "nalgebra")]
+mod pose;
+#[cfg(feature = "rtsp")]
+mod rtsp;
+#[cfg(any(test, feature = "mock"))]
+pub mod test_support;
+
+#[cfg(feature = "blocking")]
+pub use blocking::A8MiniBlocking;
+pub use builder::{A8MiniBuilder, ConnectionConfig};
+pub use error::A8MiniError;
+pub use exif::parse_jpeg_timestamp;
 
 #[derive(Debug)]
 /// Represents the A8Mini camera API with a dedicate UDP socket for both `Command`s and `HTTPQuery`s.
 pub struct A8Mini {
-    pub command_socket: UdpSocket,
+    pub command_socket: Arc<UdpSocket>,
     pub http_socket: UdpSocket,
+    http_base_url: String,
+    /// Client of every HTTP request, bound to `A8MiniBuilder::local_bind_ip`.
+    http_client: reqwest::Client,
+    media_api_path: String,
+    rtsp_main_url: String,
+    rtsp_sub_url: String,
+    /// Held for the duration of a command/ACK exchange, and by background tasks while they send.
+    command_lock: Arc<Mutex<()>>,
+    /// SEQ stamped on the next command frame, so ACKs can be matched to the frame that caused them.
+    sequence: Arc<AtomicU16>,
+    /// Model that model-specific commands are checked against. `None` disables the check.
+    model: RwLock<Option<control::CameraModel>>,
+    /// Firmware version from the last `get_firmware_version`, for capability checks.
+    firmware: RwLock<Option<control::A8MiniFirmwareVersion>>,
+    /// When the current recording was started or first seen running, for `get_recording_state`.
+    recording_since: RwLock<Option<tokio::time::Instant>>,
+    /// Retry policy of `send_http_query`; see `A8MiniBuilder::http_retry`.
+    http_attempts: u32,
+    http_retry_delay: Duration,
+    /// Size of the buffer `exchange_frame` receives ACKs into.
+    recv_buffer_size: usize,
+    /// Settings the handle was connected with; see `config`.
+    config: ConnectionConfig,
+    /// CRC16 initial value of sent and received frames; see `A8MiniBuilder::crc_init`.
+    crc_init: u16,
+    /// Rate limit of `send_command_blind`; see `A8MiniBuilder::min_command_interval`.
+    min_command_interval: Option<Duration>,
+    /// When `send_command_blind` last sent, for the rate limit.
+    last_blind_send: Arc<std::sync::Mutex<Option<tokio::time::Instant>>>,
+    /// Whether attitudes are inverted before being returned; see
+    /// `A8MiniBuilder::normalize_mounting`.
+    invert_attitude: bool,
+    /// See `A8MiniBuilder::require_limits`.
+    require_limits: bool,
+    /// Latest speed for the task started by the first `set_speed_with_coalescing`, which stops
+    /// when this is dropped.
+    speed_target: std::sync::OnceLock<watch::Sender<(i8, i8)>>,
 }
 
 impl A8Mini {
     /// Connect to and creates a new `A8Mini` using default ip address `192.168.144.25` and default port 37260 and port 82. 
     /// Remote ports are mapped to port 8080 and port 8088 on local.
     pub async fn connect() -> anyhow::Result<Self> {
-        Ok(Self::connect_to(
-            constants::CAMERA_IP,
-            constants::CAMERA_COMMAND_PORT,
-            constants::CAMERA_HTTP_PORT,
-            "8080",
-            "8088",
-        )
-        .await?)
+        Self::builder().build().await
+    }
+
+    /// Starts configuring a connection with non-default settings.
+    pub fn builder() -> A8MiniBuilder {
+        A8MiniBuilder::new()
     }
 
     /// Repeatedly tries to reconnect a total of `max_iter`` times
@@ -36,15 +96,22 @@ impl A8Mini {
         max_iter: i32,
     ) -> anyhow::Result<Self> {
         for _ in 1..max_iter {
-            let connect_attempt = Self::connect().await;
-            if connect_attempt.is_ok() {
-                return Ok(connect_attempt.unwrap());
+            if let Ok(camera) = Self::connect().await {
+                return Ok(camera);
             }
         }
 
         Err(anyhow!("max_iter reached".to_string()))
     }
 
+    /// Broadcasts a firmware version request on the command port and collects the addresses of
+    /// every camera that answers within `timeout`, for finding a camera whose IP isn't the
+    /// default. Returns an empty list if the network doesn't allow broadcast.
+    pub async fn discover(timeout: Duration) -> Result<Vec<IpAddr>, A8MiniError> {
+        let port = constants::CAMERA_COMMAND_PORT.parse().expect("default command port is numeric");
+        discover_at(SocketAddr::new(IpAddr::V4(Ipv4Addr::BROADCAST), port), timeout).await
+    }
+
     /// Connects to and creates a new `A8Mini` given network args.
     pub async fn connect_to(
         camera_ip: &str,
@@ -53,128 +120,865 @@ impl A8Mini {
         local_command_port: &str,
         local_http_port: &str,
     ) -> anyhow::Result<Self> {
+        Self::builder()
+            .camera_ip(camera_ip)
+            .camera_command_port(camera_command_port)
+            .camera_http_port(camera_http_port)
+            .local_command_port(local_command_port)
+            .local_http_port(local_http_port)
+            .build()
+            .await
+    }
+
+    pub(crate) async fn connect_with(builder: A8MiniBuilder) -> anyhow::Result<Self> {
+        let connect_timeout = builder.connect_timeout;
+        tokio::time::timeout(connect_timeout, Self::set_up(builder))
+            .await
+            .map_err(|_| {
+                error!("Connection setup took longer than {:?}.", connect_timeout);
+                A8MiniError::Timeout("connection setup")
+            })?
+    }
+
+    /// Binds and connects the sockets for `connect_with`, and runs the optional handshake.
+    async fn set_up(builder: A8MiniBuilder) -> anyhow::Result<Self> {
+        let command_bind_addr = SocketAddr::new(builder.local_bind_ip, builder.local_command_port.parse()?);
+        let http_bind_addr = SocketAddr::new(builder.local_bind_ip, builder.local_http_port.parse()?);
         debug!(
             "Binding command_socket to {} and http_socket to {}.",
-            format!("0.0.0.0:{}", local_command_port),
-            format!("0.0.0.0:{}", local_http_port)
+            command_bind_addr, http_bind_addr
         );
 
-        let camera: A8Mini = A8Mini {
-            command_socket: UdpSocket::bind(format!("0.0.0.0:{}", local_command_port)).await?,
-            http_socket: UdpSocket::bind(format!("0.0.0.0:{}", local_http_port)).await?,
+        let mut camera: A8Mini = A8Mini {
+            command_socket: Arc::new(UdpSocket::bind(command_bind_addr).await?),
+            http_socket: UdpSocket::bind(http_bind_addr).await?,
+            http_base_url: format!("http://{}:{}", builder.camera_ip, builder.camera_http_port),
+            http_client: reqwest::Client::builder()
+                .local_address((!builder.local_bind_ip.is_unspecified()).then_some(builder.local_bind_ip))
+                .build()?,
+            media_api_path: builder.media_api_path.clone(),
+            rtsp_main_url: format!("rtsp://{}:{}{}", builder.camera_ip, builder.rtsp_port, builder.rtsp_main_path),
+            rtsp_sub_url: format!("rtsp://{}:{}{}", builder.camera_ip, builder.rtsp_port, builder.rtsp_sub_path),
+            command_lock: Arc::new(Mutex::new(())),
+            sequence: Arc::new(AtomicU16::new(0)),
+            model: RwLock::new(None),
+            firmware: RwLock::new(None),
+            recording_since: RwLock::new(None),
+            http_attempts: builder.http_attempts,
+            http_retry_delay: builder.http_retry_delay,
+            recv_buffer_size: builder.recv_buffer_size,
+            config: ConnectionConfig {
+                camera_ip: builder.camera_ip.clone(),
+                command_port: builder.camera_command_port.clone(),
+                http_port: builder.camera_http_port.clone(),
+                local_command_port: builder.local_command_port.clone(),
+                local_http_port: builder.local_http_port.clone(),
+                recv_timeout: builder.recv_timeout,
+            },
+            crc_init: builder.crc_init,
+            min_command_interval: builder.min_command_interval,
+            last_blind_send: Arc::new(std::sync::Mutex::new(None)),
+            invert_attitude: false,
+            require_limits: builder.require_limits,
+            speed_target: std::sync::OnceLock::new(),
         };
 
+        let command_address = format!("{}:{}", builder.camera_ip, builder.camera_command_port);
         camera
             .command_socket
-            .connect(format!("{}:{}", camera_ip, camera_command_port))
-            .await?;
+            .connect(&command_address)
+            .await
+            .map_err(|e| unreachable_or(e, &command_address))?;
         info!("Connected a8mini command_socket.");
 
+        let http_address = format!("{}:{}", builder.camera_ip, builder.camera_http_port);
         camera
             .http_socket
-            .connect(format!("{}:{}", camera_ip, camera_http_port))
-            .await?;
+            .connect(&http_address)
+            .await
+            .map_err(|e| unreachable_or(e, &http_address))?;
         info!("Connected a8mini http_socket.");
 
+        if builder.verify_on_connect {
+            if let Err(e) = camera.send_command(control::A8MiniSimpleCommand::FirmwareVersionInformation).await {
+                warn!("Connection handshake with {} failed: {}", command_address, e);
+                if matches!(e.downcast_ref(), Some(A8MiniError::NetworkUnreachable(_))) {
+                    return Err(e);
+                }
+                return Err(A8MiniError::NoCameraFound(command_address).into());
+            }
+            info!("Camera answered connection handshake.");
+        }
+
+        if builder.normalize_mounting {
+            let mounting = camera.mounting_direction().await?;
+            info!("Gimbal mounted {:?}.", mounting);
+            camera.invert_attitude = mounting == control::MountingDirection::UpsideDown;
+        }
+
         Ok(camera)
     }
 
+    /// Settings this handle was connected with. `A8MiniBuilder::from_config` connects again
+    /// with the same settings.
+    pub fn config(&self) -> &ConnectionConfig {
+        &self.config
+    }
+
+    /// Local address the command socket is bound to, e.g. to learn an ephemeral port.
+    pub fn command_local_addr(&self) -> Result<SocketAddr, A8MiniError> {
+        Ok(self.command_socket.local_addr()?)
+    }
+
+    /// Camera address the command socket is connected to.
+    pub fn command_peer_addr(&self) -> Result<SocketAddr, A8MiniError> {
+        Ok(self.command_socket.peer_addr()?)
+    }
+
+    /// Local address the HTTP socket is bound to.
+    pub fn http_local_addr(&self) -> Result<SocketAddr, A8MiniError> {
+        Ok(self.http_socket.local_addr()?)
+    }
+
+    /// Camera address the HTTP socket is connected to.
+    pub fn http_peer_addr(&self) -> Result<SocketAddr, A8MiniError> {
+        Ok(self.http_socket.peer_addr()?)
+    }
+
+    /// URL of the live main video stream, for GStreamer/ffmpeg.
+    pub fn rtsp_url(&self) -> String {
+        self.rtsp_main_url.clone()
+    }
+
+    /// URL of the live sub (lower resolution) video stream.
+    pub fn rtsp_sub_url(&self) -> String {
+        self.rtsp_sub_url.clone()
+    }
+
     /// Sends a `control::Command` blind. This should be used for all commands that don't have a ACK.
+    /// Waits for any in-flight `send_command` exchange to finish first, and for the
+    /// `A8MiniBuilder::min_command_interval` since the previous blind send, if set.
+    #[instrument(level = "debug", skip(self))]
     pub async fn send_command_blind<T: control::Command>(
         &self,
         command: T,
     ) -> anyhow::Result<()> {
-       
+        self.check_supported(&command)?;
+        self.check_limits_known(&command).await?;
+        let mut frame = [0u8; constants::MAX_FRAME_LEN];
+        let frame_len = command.write_to(&mut frame)?;
+        let _exchange =
+            lock_for_blind_send(&self.command_lock, &self.last_blind_send, self.min_command_interval).await;
+        self.send_frame(&mut frame[..frame_len]).await
+    }
+
+    /// Sends a `control::Command` expecting an ACK. Returns the parsed ACK frame.
+    ///
+    /// The send and receive happen under a lock, so concurrent callers can't steal each other's
+    /// ACKs. Frames for other command IDs or sequence numbers (e.g. late ACKs of blind sends)
+    /// are skipped. Fails with `A8MiniError::Timeout` if no matching ACK arrives in time, and
+    /// with `A8MiniError::CameraNack` if the ACK reports that the camera rejected the command.
+    #[instrument(level = "debug", skip(self))]
+    pub async fn send_command<T: control::Command>(
+        &self,
+        command: T,
+    ) -> anyhow::Result<control::AckFrame> {
+        self.check_supported(&command)?;
+        self.check_limits_known(&command).await?;
+        let mut frame = [0u8; constants::MAX_FRAME_LEN];
+        let frame_len = command.write_to(&mut frame)?;
+        let _exchange = self.command_lock.lock().await;
+        let ack = self.exchange_frame(&mut frame[..frame_len]).await?;
+        ack.check_status()?;
+        Ok(ack)
+    }
 
-        let send_len = self.command_socket.send(command.to_bytes().as_slice()).await?;
+    /// Same as `send_command`, decoding the ACK according to its command ID.
+    pub async fn send_command_typed<T: control::Command>(&self, command: T) -> anyhow::Result<control::AckPayload> {
+        let frame = self.send_command(command).await?;
+        Ok(control::AckPayload::from_frame(&frame)?)
+    }
 
-        if send_len == 0 {
-            error!("No command bytes sent.");
-            return Err(anyhow!("No command bytes sent.".to_string()));
+    /// Same as `send_command`, resending the command up to `attempts` times when no ACK arrives
+    /// within the receive timeout. Each attempt gets a new sequence number, so a late ACK of an
+    /// earlier attempt is never mistaken for the current one.
+    ///
+    /// 3 attempts cover the occasional dropped packet on a healthy link; 5 to 10 suit a noisy
+    /// WiFi link, and 0 counts as 1. Only use it for idempotent commands: a lost ACK means the camera may have
+    /// executed the command more than once.
+    #[instrument(level = "debug", skip(self))]
+    pub async fn send_command_retry<T: control::Command>(
+        &self,
+        command: T,
+        attempts: u32,
+    ) -> anyhow::Result<control::AckFrame> {
+        self.check_supported(&command)?;
+        self.check_limits_known(&command).await?;
+        let mut frame = [0u8; constants::MAX_FRAME_LEN];
+        let frame_len = command.write_to(&mut frame)?;
+        let _exchange = self.command_lock.lock().await;
+
+        let attempts = attempts.max(1);
+        for attempt in 1..=attempts {
+            // each attempt stamps its own sequence number over the previous one
+            match self.exchange_frame(&mut frame[..frame_len]).await {
+                Err(e) if matches!(e.downcast_ref(), Some(A8MiniError::Timeout(_))) => {
+                    warn!("No ACK on attempt {}/{}.", attempt, attempts);
+                }
+                result => {
+                    let ack = result?;
+                    ack.check_status()?;
+                    return Ok(ack);
+                }
+            }
         }
 
+        Err(A8MiniError::Timeout("command ACK").into())
+    }
+
+    /// Model that model-specific commands are checked against, as detected by `get_camera_info`
+    /// or set with `set_model`. `None` until then.
+    pub fn model(&self) -> Option<control::CameraModel> {
+        self.model.read().unwrap().clone()
+    }
+
+    /// Overrides the model that model-specific commands are checked against. `None` turns the
+    /// check off, for users who know their camera supports a command.
+    pub fn set_model(&self, model: Option<control::CameraModel>) {
+        *self.model.write().unwrap() = model;
+    }
 
+    /// Fails with `A8MiniError::UnsupportedByModel` if the known model lacks `command`.
+    fn check_supported<T: control::Command>(&self, command: &T) -> Result<(), A8MiniError> {
+        match &*self.model.read().unwrap() {
+            Some(model) if !command.supported_by(model) => Err(A8MiniError::UnsupportedByModel {
+                command: format!("{:?}", command),
+                model: model.clone(),
+            }),
+            _ => Ok(()),
+        }
+    }
 
+    /// With `A8MiniBuilder::require_limits`, fails with `A8MiniError::ModelUnknown` if `command`
+    /// moves the gimbal and its limits can't be established.
+    async fn check_limits_known<T: control::Command>(&self, command: &T) -> anyhow::Result<()> {
+        if command.moves_gimbal() {
+            self.known_limits().await?;
+        }
         Ok(())
     }
 
-    /// Sends a `control::Command` expecting an ACK. Returns received ACK response bytes.
-    pub async fn send_command<T: control::Command>(
-        &self,
-        command: T,
-    ) -> anyhow::Result<[u8; constants::RECV_BUFF_SIZE]> {
-        self.send_command_blind(command).await?;
-        let mut recv_buffer = [0; constants::RECV_BUFF_SIZE];
+    /// `limits()`, but with `A8MiniBuilder::require_limits` set, first detects the model if it
+    /// isn't known and fails with `A8MiniError::ModelUnknown` if that doesn't work.
+    async fn known_limits(&self) -> anyhow::Result<control::GimbalLimits> {
+        if !self.require_limits {
+            return Ok(self.limits());
+        }
+        let model = match self.model() {
+            Some(model) => model,
+            None => {
+                // exchanged directly: `send_command` checks limits itself, so can't be awaited here
+                let mut frame = control::Command::to_bytes(&control::A8MiniSimpleCommand::HardwareIDInformation);
+                let exchange = async {
+                    let _exchange = self.command_lock.lock().await;
+                    self.exchange_frame(&mut frame).await
+                };
+                let hardware_id = exchange.await.map_err(|e| {
+                    warn!("Couldn't detect the gimbal model: {}", e);
+                    A8MiniError::ModelUnknown
+                })?;
+                let model = control::CameraModel::from_hardware_id(&hardware_id.payload);
+                self.set_model(Some(model.clone()));
+                model
+            }
+        };
+        if let control::CameraModel::Unknown(_) = model {
+            return Err(A8MiniError::ModelUnknown.into());
+        }
+        Ok(control::GimbalLimits::for_model(&model))
+    }
+
+    /// Frames `payload` under an arbitrary `command_id` and sends it expecting an ACK, for
+    /// commands this crate doesn't model. The ACK is matched and validated like `send_command`.
+    pub async fn send_raw(&self, command_id: u8, payload: &[u8]) -> anyhow::Result<control::AckFrame> {
+        let _exchange = self.command_lock.lock().await;
+        let ack = self.exchange_frame(&mut control::build_frame(command_id, payload)).await?;
+        ack.check_status()?;
+        Ok(ack)
+    }
+
+    /// Same as `send_raw` for commands that aren't ACKed. The frame still gets a fresh sequence
+    /// number, so a late ACK can't be mistaken for the answer to another command, and is paced
+    /// like `send_command_blind`.
+    pub async fn send_raw_blind(&self, command_id: u8, payload: &[u8]) -> anyhow::Result<()> {
+        let mut frame = control::build_frame(command_id, payload);
+        let _exchange =
+            lock_for_blind_send(&self.command_lock, &self.last_blind_send, self.min_command_interval).await;
+        control::stamp_sequence(&mut frame, self.sequence.fetch_add(1, Ordering::Relaxed))?;
+        self.send_frame(&mut frame).await
+    }
+
+    /// Stamps a fresh sequence number on `frame_bytes`, sends it and waits for the ACK with the
+    /// same command ID and sequence number. Callers must hold `command_lock`.
+    async fn exchange_frame(&self, frame_bytes: &mut [u8]) -> anyhow::Result<control::AckFrame> {
+        let command_id = frame_bytes[7];
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed);
+        control::stamp_sequence(frame_bytes, sequence)?;
+
+        let sent_at = tokio::time::Instant::now();
+        self.send_frame(frame_bytes).await?;
+        let mut recv_buffer = vec![0; self.recv_buffer_size];
 
         debug!("Waiting for command response.");
 
-        let recv_len = timeout(
-            constants::RECV_TIMEOUT,
-            self.command_socket.recv(&mut recv_buffer),
-        )
-        .await??;
-        if recv_len == 0 {
-            error!("No command bytes received.");
-            return Err(anyhow!("No bytes received.".to_string()));
+        let deadline = tokio::time::Instant::now() + self.config.recv_timeout;
+        loop {
+            let recv_len = tokio::time::timeout_at(
+                deadline,
+                self.command_socket.recv(&mut recv_buffer),
+            )
+            .await
+            .map_err(|_| A8MiniError::Timeout("command ACK"))??;
+            if recv_len == 0 {
+                error!("No command bytes received.");
+                return Err(anyhow!("No bytes received.".to_string()));
+            }
+
+            debug!(
+                "Command response of size {} received after {:?}: {:02x?}",
+                recv_len,
+                sent_at.elapsed(),
+                &recv_buffer[..recv_len]
+            );
+            if recv_len == recv_buffer.len() {
+                warn!(
+                    "Command response filled the whole {}-byte receive buffer and may be truncated; \
+                     see A8MiniBuilder::recv_buffer_size.",
+                    recv_len
+                );
+            }
+
+            let frame = control::AckFrame::parse_with_crc_init(&recv_buffer[..recv_len], self.crc_init)
+                .inspect_err(|e| warn!("Dropping malformed ACK: {}", e))?;
+            if frame.command_id == command_id && frame.sequence == sequence {
+                return Ok(frame);
+            }
+            debug!(
+                "Skipping frame for command {:#04x} seq {}, waiting for {:#04x} seq {}.",
+                frame.command_id, frame.sequence, command_id, sequence
+            );
         }
+    }
 
-        debug!(
-            "Command response of size {} received successfully: {:?}",
-            recv_len, 
-            recv_buffer
-        );
+    /// Writes one frame to the command socket. Callers must hold `command_lock`.
+    async fn send_frame(&self, frame_bytes: &mut [u8]) -> anyhow::Result<()> {
+        self.seal(frame_bytes);
+        trace!("Sending frame: {:02x?}", frame_bytes);
+        send_datagram(&*self.command_socket, frame_bytes).await.inspect_err(|e| error!("{}", e))
+    }
+
+    /// Yields every frame the camera sends on the command socket, without sending anything, e.g.
+    /// to log data pushed unsolicited while a physical remote is in control. Malformed datagrams
+    /// yield an error and the stream carries on; it only ends if the socket fails.
+    ///
+    /// This reads from the same socket `send_command` waits for ACKs on, so the two steal each
+    /// other's frames. Use the stream exclusively, not alongside commands on this handle.
+    pub fn raw_frame_stream(&self) -> impl Stream<Item = Result<control::AckFrame, A8MiniError>> + '_ {
+        stream::unfold(vec![0; self.recv_buffer_size], move |mut recv_buffer| async move {
+            let item = match self.command_socket.recv(&mut recv_buffer).await {
+                Ok(recv_len) => {
+                    trace!("Received frame: {:02x?}", &recv_buffer[..recv_len]);
+                    control::AckFrame::parse_with_crc_init(&recv_buffer[..recv_len], self.crc_init)
+                }
+                Err(e) => {
+                    warn!("Raw frame stream stopped: {}", e);
+                    return None;
+                }
+            };
+            Some((item, recv_buffer))
+        })
+    }
+
+    /// Recomputes the CRC of a frame built with `constants::CRC16_INIT` if this handle uses
+    /// another initial value.
+    fn seal(&self, frame_bytes: &mut [u8]) {
+        seal(frame_bytes, self.crc_init);
+    }
+
+    /// `Command::to_bytes`, sealed with this handle's CRC initial value.
+    fn encode<T: control::Command>(&self, command: &T) -> Vec<u8> {
+        let mut frame_bytes = command.to_bytes();
+        self.seal(&mut frame_bytes);
+        frame_bytes
+    }
+
+    /// Sends each command blind, then waits its duration before the next, so gimbal motions can
+    /// be scripted as data. Stops at the first failing step; the error names the step's index.
+    pub async fn run_sequence(&self, steps: &[(control::A8MiniCommand, Duration)]) -> anyhow::Result<()> {
+        for (index, (command, delay)) in steps.iter().enumerate() {
+            self.send_command_blind(*command)
+                .await
+                .with_context(|| format!("sequence step {} ({:?}) failed", index, command))?;
+            tokio::time::sleep(*delay).await;
+        }
+
+        Ok(())
+    }
+
+    /// Spawns a task sending `Heartbeat` every `interval` to keep the link alive.
+    /// The task stops when the returned handle is dropped.
+    pub fn spawn_heartbeat(&self, interval: Duration) -> HeartbeatHandle {
+        let command_socket = self.command_socket.clone();
+        let command_lock = self.command_lock.clone();
+        let heartbeat = self.encode(&control::A8MiniSimpleCommand::Heartbeat);
+
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+
+                // never land between another caller's command and its ACK
+                let _exchange = command_lock.lock().await;
+                if let Err(e) = command_socket.send(&heartbeat).await {
+                    warn!("Failed to send heartbeat: {}", e);
+                }
+            }
+        });
+
+        HeartbeatHandle { task }
+    }
+
+    /// Angles the connected gimbal can be pointed at, per its detected model. Falls back to
+    /// `GimbalLimits::A8_MINI` while the model is unknown; see `get_camera_info`.
+    pub fn limits(&self) -> control::GimbalLimits {
+        self.model()
+            .map_or(control::GimbalLimits::A8_MINI, |model| control::GimbalLimits::for_model(&model))
+    }
+
+    /// Points the gimbal at an absolute yaw/pitch given in degrees.
+    /// Fails with `A8MiniError::OutOfRange` outside `limits().commandable()`.
+    pub async fn set_angle_deg(&self, yaw_deg: f32, pitch_deg: f32) -> anyhow::Result<()> {
+        let limits = self.known_limits().await?;
+        let command = control::A8MiniComplexCommand::yaw_pitch_angle_deg_within(yaw_deg, pitch_deg, &limits)?;
+        self.send_command_blind(command).await
+    }
+
+    /// Pans to an absolute yaw/pitch at `speed` degrees per second (1 to 100) instead of as
+    /// fast as the gimbal allows. The protocol has no speed for absolute angles, as `0x0E`
+    /// carries only yaw and pitch, so this sends angle setpoints stepping from the current
+    /// attitude to the target every `constants::FOLLOW_COMMAND_INTERVAL`, returning once the
+    /// target itself is sent.
+    pub async fn set_angle_with_speed(&self, yaw_deg: f32, pitch_deg: f32, speed: u8) -> anyhow::Result<()> {
+        self.known_limits().await?.commandable().check(yaw_deg, pitch_deg)?;
+        if !(1..=100).contains(&speed) {
+            return Err(A8MiniError::OutOfRange { name: "speed", value: speed as f32, min: 1.0, max: 100.0 }.into());
+        }
+
+        let start = self.get_attitude_information().await?;
+        let (start_yaw, start_pitch) = (start.yaw_deg(), start.pitch_deg());
+        let distance = (yaw_deg - start_yaw).abs().max((pitch_deg - start_pitch).abs());
+        let duration = Duration::from_secs_f32(distance / speed as f32);
+
+        let started = tokio::time::Instant::now();
+        let mut ticker = tokio::time::interval(constants::FOLLOW_COMMAND_INTERVAL);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            let elapsed = started.elapsed();
+            if elapsed >= duration {
+                break;
+            }
+            let progress = elapsed.as_secs_f32() / duration.as_secs_f32();
+            let yaw = start_yaw + (yaw_deg - start_yaw) * progress;
+            let pitch = start_pitch + (pitch_deg - start_pitch) * progress;
+            self.set_angle_deg(yaw, pitch).await?;
+        }
+        self.set_angle_deg(yaw_deg, pitch_deg).await
+    }
+
+    /// Turns the gimbal at speeds given as joystick axes in `[-1.0, 1.0]`, ignoring deflections
+    /// within `deadband`. See `A8MiniComplexCommand::yaw_pitch_speed_normalized`.
+    pub async fn set_speed_normalized(&self, yaw: f32, pitch: f32, deadband: f32) -> anyhow::Result<()> {
+        self.send_command_blind(control::A8MiniComplexCommand::yaw_pitch_speed_normalized(yaw, pitch, deadband))
+            .await
+    }
+
+    /// Starts controlling the gimbal by speed. The returned guard sets speeds and sends
+    /// `StopRotation` when dropped, so an early return or panic doesn't leave the gimbal turning.
+    pub fn speed_session(&self) -> SpeedGuard<'_> {
+        SpeedGuard { camera: self, armed: true }
+    }
+
+    /// Turns the gimbal at raw speeds in `-100..=100` for teleop over slow links. Nonzero speeds
+    /// are handed to a background task that keeps only the latest and sends it at most every
+    /// `constants::SPEED_COALESCE_INTERVAL`, and no sooner than `A8MiniBuilder::min_command_interval`
+    /// allows, so rapid stick updates don't queue up. A zero speed is sent right away instead,
+    /// bypassing the rate limit, and no earlier speed is sent after it.
+    pub async fn set_speed_with_coalescing(&self, yaw: i8, pitch: i8) -> anyhow::Result<()> {
+        let command = control::A8MiniComplexCommand::SetYawPitchSpeed(yaw, pitch);
+        self.check_supported(&command)?;
+        self.check_limits_known(&command).await?;
+
+        let target = self.speed_target.get_or_init(|| self.spawn_speed_coalescer());
+        if (yaw, pitch) != (0, 0) {
+            target.send_replace((yaw, pitch));
+            return Ok(());
+        }
+
+        // the task reads the target under the lock, so it can't send a stale speed after this
+        let _exchange = self.command_lock.lock().await;
+        target.send_if_modified(|speed| {
+            *speed = (0, 0);
+            false
+        });
+        *self.last_blind_send.lock().unwrap() = Some(tokio::time::Instant::now());
+        self.send_frame(&mut self.encode(&command)).await
+    }
+
+    /// Starts the task sending the latest nonzero speed of `set_speed_with_coalescing`. It stops
+    /// once the returned sender, kept in `speed_target`, is dropped with the handle.
+    fn spawn_speed_coalescer(&self) -> watch::Sender<(i8, i8)> {
+        let (target, mut latest) = watch::channel((0, 0));
+        let command_socket = self.command_socket.clone();
+        let command_lock = self.command_lock.clone();
+        let last_blind_send = self.last_blind_send.clone();
+        let min_command_interval = self.min_command_interval;
+        let crc_init = self.crc_init;
+
+        tokio::spawn(async move {
+            while latest.changed().await.is_ok() {
+                {
+                    let _exchange =
+                        lock_for_blind_send(&command_lock, &last_blind_send, min_command_interval).await;
+                    let (yaw, pitch) = *latest.borrow_and_update();
+                    if (yaw, pitch) == (0, 0) {
+                        // stopped by the caller while this waited
+                        continue;
+                    }
+                    let speed = control::A8MiniComplexCommand::SetYawPitchSpeed(yaw, pitch);
+                    let mut frame = control::Command::to_bytes(&speed);
+                    seal(&mut frame, crc_init);
+                    if let Err(e) = command_socket.send(&frame).await {
+                        warn!("Failed to send coalesced speed: {}", e);
+                    }
+                }
+                tokio::time::sleep(constants::SPEED_COALESCE_INTERVAL).await;
+            }
+        });
+
+        target
+    }
+
+    /// Points the gimbal at `pitch_deg`, holding the current yaw.
+    pub async fn set_pitch_deg(&self, pitch_deg: f32) -> anyhow::Result<()> {
+        let attitude = self.get_fresh_attitude().await?;
+        self.set_angle_deg(attitude.yaw_deg(), pitch_deg).await
+    }
+
+    /// Points the gimbal at `yaw_deg`, holding the current pitch.
+    pub async fn set_yaw_deg(&self, yaw_deg: f32) -> anyhow::Result<()> {
+        let attitude = self.get_fresh_attitude().await?;
+        self.set_angle_deg(yaw_deg, attitude.pitch_deg()).await
+    }
+
+    /// Points the gimbal at each (yaw_deg, pitch_deg) bearing from `bearings` until the stream
+    /// ends, for tracking a target. Angle commands go out at most every
+    /// `constants::FOLLOW_COMMAND_INTERVAL`, each carrying the newest bearing; bearings that
+    /// arrive in between are dropped, so the gimbal never lags behind the stream. A bearing is
+    /// also dropped if another exchange holds the command socket or its send buffer is full.
+    /// Bearings outside `limits().commandable()` are clamped to them; NaN or infinite ones, e.g.
+    /// from a tracker that lost the target, are skipped.
+    pub async fn follow_target(&self, bearings: impl Stream<Item = (f32, f32)>) -> anyhow::Result<()> {
+        let mut bearings = std::pin::pin!(bearings);
+        let mut ticker = tokio::time::interval(constants::FOLLOW_COMMAND_INTERVAL);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let limits = self.known_limits().await?.commandable();
+        let mut pending = None;
+
+        loop {
+            tokio::select! {
+                biased;
+                bearing = bearings.next() => match bearing {
+                    Some((yaw_deg, pitch_deg)) if !(yaw_deg.is_finite() && pitch_deg.is_finite()) => {
+                        warn!("Skipping non-finite bearing ({}, {}).", yaw_deg, pitch_deg);
+                    }
+                    Some(bearing) => pending = Some(bearing),
+                    None => break,
+                },
+                _ = ticker.tick(), if pending.is_some() => {
+                    if let Some((yaw_deg, pitch_deg)) = pending.take() {
+                        self.try_point(yaw_deg, pitch_deg, &limits)?;
+                    }
+                }
+            }
+        }
+
+        // the last bearing is where the target was last seen
+        if let Some((yaw_deg, pitch_deg)) = pending {
+            let (yaw_deg, pitch_deg) = limits.clamp(yaw_deg, pitch_deg);
+            self.send_command_blind(control::A8MiniComplexCommand::yaw_pitch_angle_deg_within(yaw_deg, pitch_deg, &limits)?)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Sends one `follow_target` angle command without waiting, dropping it if the command socket
+    /// is busy.
+    fn try_point(&self, yaw_deg: f32, pitch_deg: f32, limits: &control::GimbalLimits) -> anyhow::Result<()> {
+        let (yaw_deg, pitch_deg) = limits.clamp(yaw_deg, pitch_deg);
+        let command = control::A8MiniComplexCommand::yaw_pitch_angle_deg_within(yaw_deg, pitch_deg, limits)?;
+
+        let Ok(_exchange) = self.command_lock.try_lock() else {
+            debug!("Command socket busy, dropping bearing ({}, {}).", yaw_deg, pitch_deg);
+            return Ok(());
+        };
+        let mut frame = [0u8; constants::MAX_FRAME_LEN];
+        let frame_len = control::Command::write_to(&command, &mut frame)?;
+        self.seal(&mut frame[..frame_len]);
+        if !try_send_datagram(&*self.command_socket, &frame[..frame_len])? {
+            debug!("Command socket saturated, dropping bearing ({}, {}).", yaw_deg, pitch_deg);
+        }
+        Ok(())
+    }
+
+    /// Reads the attitude, failing with `A8MiniError::Timeout` if the answer takes longer than
+    /// `constants::ATTITUDE_MAX_AGE`, by which time the gimbal may have moved on.
+    async fn get_fresh_attitude(&self) -> anyhow::Result<control::A8MiniAttitude> {
+        tokio::time::timeout(constants::ATTITUDE_MAX_AGE, self.get_attitude_information())
+            .await
+            .map_err(|_| A8MiniError::Timeout("fresh attitude"))?
+    }
+
+    /// Reads the attitude with less overhead than `get_attitude_information`: the request frame
+    /// is prebuilt and the ACK is decoded without allocating. Meant for high-rate logging loops.
+    pub async fn poll_attitude(&self) -> Result<control::A8MiniAttitude, A8MiniError> {
+        let (attitude, _) = self.timed_attitude().await?;
+        Ok(self.upright(attitude))
+    }
+
+    /// `request_attitude` on this handle's socket: the raw attitude and its round trip.
+    async fn timed_attitude(&self) -> Result<(control::A8MiniAttitude, Duration), A8MiniError> {
+        request_attitude(
+            &self.command_socket,
+            &self.command_lock,
+            &self.sequence,
+            self.config.recv_timeout,
+            self.crc_init,
+        )
+        .await
+    }
+
+    /// Applies `A8MiniBuilder::normalize_mounting` to a decoded attitude.
+    fn upright(&self, attitude: control::A8MiniAttitude) -> control::A8MiniAttitude {
+        if self.invert_attitude {
+            attitude.inverted()
+        } else {
+            attitude
+        }
+    }
 
-        Ok(recv_buffer)
+    /// Measures the round trip of an attitude request to its ACK, for monitoring link quality.
+    /// Heartbeats aren't ACKed, so the attitude request stands in. Timing starts once the
+    /// command lock is held, so waiting for other exchanges isn't counted. Fails with
+    /// `A8MiniError::Timeout` if no ACK arrives within the receive timeout.
+    pub async fn ping_latency(&self) -> Result<Duration, A8MiniError> {
+        let (_, latency) = self.timed_attitude().await?;
+        trace!("Ping answered after {:?}.", latency);
+        Ok(latency)
     }
 
     /// Retrieves attitude information from the camera. 
     pub async fn get_attitude_information(
         &self,
     ) -> anyhow::Result<control::A8MiniAttitude> {
-        let response = self
+        let frame = self
             .send_command(control::A8MiniSimpleCommand::AttitudeInformation)
             .await?;
 
-        
-        if response.len() < 20 {
-             return Err(anyhow::anyhow!("Response too short to contain attitude data"));
+        Ok(self.upright(control::A8MiniAttitude::from_payload(&frame.payload)?))
+    }
+
+    /// Sends `AutoCenter` and polls the attitude until yaw and pitch are within
+    /// `constants::CENTER_TOLERANCE_DEG` of zero. Fails with `A8MiniError::Timeout` if the
+    /// gimbal hasn't settled after `timeout`.
+    pub async fn auto_center_and_wait(&self, timeout: Duration) -> anyhow::Result<control::A8MiniAttitude> {
+        self.send_command_blind(control::A8MiniSimpleCommand::AutoCenter).await?;
+
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let attitude = self.get_attitude_information().await?;
+            if attitude.yaw_deg().abs() <= constants::CENTER_TOLERANCE_DEG
+                && attitude.pitch_deg().abs() <= constants::CENTER_TOLERANCE_DEG
+            {
+                return Ok(attitude);
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(A8MiniError::Timeout("gimbal to center").into());
+            }
+            tokio::time::sleep(constants::ATTITUDE_POLL_INTERVAL).await;
         }
-        
-        let data_slice = &response[8..20];
-        let attitude_info: control::A8MiniAttitude = deserialize(data_slice)?;
-        
-        Ok(attitude_info)
+    }
+
+    /// Points the gimbal at an absolute yaw/pitch and polls the attitude until both are within
+    /// `tolerance_deg`, re-sending the angle whenever the pointing error grows between polls.
+    /// Returns the final measured attitude, or `A8MiniError::Timeout` if it never gets there.
+    pub async fn point_at(
+        &self,
+        yaw_deg: f32,
+        pitch_deg: f32,
+        tolerance_deg: f32,
+        timeout: Duration,
+    ) -> anyhow::Result<control::A8MiniAttitude> {
+        self.set_angle_deg(yaw_deg, pitch_deg).await?;
+
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut last_error = f32::INFINITY;
+        loop {
+            let attitude = self.get_attitude_information().await?;
+            let yaw_error = (attitude.yaw_deg() - yaw_deg).abs();
+            let pitch_error = (attitude.pitch_deg() - pitch_deg).abs();
+            if yaw_error <= tolerance_deg && pitch_error <= tolerance_deg {
+                return Ok(attitude);
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(A8MiniError::Timeout("gimbal to reach target angle").into());
+            }
+
+            let error = yaw_error.max(pitch_error);
+            if error > last_error {
+                debug!("Pointing error grew to {}°, re-commanding angle.", error);
+                self.set_angle_deg(yaw_deg, pitch_deg).await?;
+            }
+            last_error = error;
+            tokio::time::sleep(constants::ATTITUDE_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Polls the attitude at `rate_hz` for `duration`, writing the CSV header and then one
+    /// timestamped row per reading to `writer`. Polls that get no answer are skipped; write
+    /// errors stop logging and are returned. Returns the number of rows written.
+    pub async fn log_attitude_csv<W: AsyncWrite + Unpin>(
+        &self,
+        writer: &mut W,
+        rate_hz: u32,
+        duration: Duration,
+    ) -> anyhow::Result<u64> {
+        writer.write_all(control::A8MiniAttitude::CSV_HEADER.as_bytes()).await?;
+        self.log_attitude(writer, rate_hz, duration, control::A8MiniAttitude::to_csv_row)
+            .await
+    }
+
+    /// Same as `log_attitude_csv`, writing one JSON object per reading and line (NDJSON) with
+    /// no header, for `jq` and log ingestion pipelines.
+    pub async fn log_attitude_ndjson<W: AsyncWrite + Unpin>(
+        &self,
+        writer: &mut W,
+        rate_hz: u32,
+        duration: Duration,
+    ) -> anyhow::Result<u64> {
+        self.log_attitude(writer, rate_hz, duration, control::A8MiniAttitude::to_ndjson_line)
+            .await
+    }
+
+    /// Shared loop of `log_attitude_csv` and `log_attitude_ndjson`. `format` turns a reading and
+    /// its timestamp into one complete, newline-terminated record.
+    async fn log_attitude<W: AsyncWrite + Unpin>(
+        &self,
+        writer: &mut W,
+        rate_hz: u32,
+        duration: Duration,
+        format: fn(&control::A8MiniAttitude, chrono::DateTime<chrono::Utc>) -> String,
+    ) -> anyhow::Result<u64> {
+        let deadline = tokio::time::Instant::now() + duration;
+        let mut ticker = tokio::time::interval(Duration::from_secs(1) / rate_hz.max(1));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut rows: u64 = 0;
+
+        while ticker.tick().await < deadline {
+            match self.get_attitude_information().await {
+                Ok(attitude) => {
+                    writer.write_all(format(&attitude, chrono::Utc::now()).as_bytes()).await?;
+                    rows += 1;
+                }
+                Err(e) => warn!("Skipping attitude row: {}", e),
+            }
+        }
+        writer.flush().await?;
+
+        Ok(rows)
     }
 
     pub fn stream_attitude_data(self, target_hz: u64) -> mpsc::Receiver<control::A8MiniAttitude> {
+        self.stream_attitude_data_with_stats(target_hz).0
+    }
+
+    /// Same as `stream_attitude_data`, also returning counters of the polls and their outcomes
+    /// for showing link quality while streaming.
+    pub fn stream_attitude_data_with_stats(
+        self,
+        target_hz: u64,
+    ) -> (mpsc::Receiver<control::A8MiniAttitude>, AttitudeStats) {
+        self.spawn_attitude_poller(target_hz, |_, attitude| attitude)
+    }
+
+    /// Same as `stream_attitude_data`, pairing each attitude with the monotonic time it was
+    /// received, for computing the interval between samples in sensor fusion. The timestamp is
+    /// taken right after the UDP receive completes, before the frame is decoded. Unlike
+    /// wall-clock time it never jumps, e.g. when NTP adjusts the clock.
+    pub fn stream_attitude_data_timestamped(
+        self,
+        target_hz: u64,
+    ) -> mpsc::Receiver<(tokio::time::Instant, control::A8MiniAttitude)> {
+        self.spawn_attitude_poller(target_hz, |received_at, attitude| (received_at, attitude)).0
+    }
+
+    /// Runs the polling task of the `stream_attitude_data*` methods, sending `sample(received_at,
+    /// attitude)` for every attitude frame. A `target_hz` of 0 is taken as 1.
+    fn spawn_attitude_poller<T: Send + 'static>(
+        self,
+        target_hz: u64,
+        sample: fn(tokio::time::Instant, control::A8MiniAttitude) -> T,
+    ) -> (mpsc::Receiver<T>, AttitudeStats) {
         // Create a channel with a buffer of 100 packets
         let (tx, rx) = mpsc::channel(100);
+        let stats = AttitudeStats::default();
+        let counters = stats.counters.clone();
         
-        // Calculate sleep time (e.g., 100Hz = 10ms)
-        let interval_ms = 1000 / target_hz;
+        // Calculate sleep time (e.g., 100Hz = 10ms); 0Hz polls at 1Hz
+        let interval_ms = 1000 / target_hz.max(1);
 
         tokio::spawn(async move {
             let mut buffer = [0u8; 128];
             
             loop {
-                if let Err(_) = self.send_command_blind(control::A8MiniSimpleCommand::AttitudeInformation).await {
+                if self.send_command_blind(control::A8MiniSimpleCommand::AttitudeInformation).await.is_ok() {
+                    counters.polls_sent.fetch_add(1, Ordering::Relaxed);
                 }
 
                 let recv_future = self.command_socket.recv_from(&mut buffer);
-                match timeout(std::time::Duration::from_millis(50), recv_future).await {
-                    Ok(Ok((len, _))) => {
+                let received = timeout(std::time::Duration::from_millis(50), recv_future).await;
+                let received_at = tokio::time::Instant::now();
+                match received {
+                    Ok(Ok((len, _))) => match control::AckFrame::parse_with_crc_init(&buffer[..len], self.crc_init) {
                         // Check for correct Packet ID (0x0D)
-                        if len >= 20 && buffer[7] == 0x0D {
-                            let data_slice = &buffer[8..20];
-                            if let Ok(att) = deserialize::<control::A8MiniAttitude>(data_slice) {
-                                
-                                if tx.send(att).await.is_err() {
-                                    break; 
+                        Ok(frame) if frame.command_id == 0x0D => {
+                            if let Ok(att) = control::A8MiniAttitude::from_payload(&frame.payload) {
+                                counters.frames_received.fetch_add(1, Ordering::Relaxed);
+                                if tx.send(sample(received_at, self.upright(att))).await.is_err() {
+                                    break;
                                 }
                             }
                         }
+                        Ok(_) => {}
+                        Err(A8MiniError::CrcMismatch { .. }) => {
+                            counters.crc_failures.fetch_add(1, Ordering::Relaxed);
+                        }
+                        Err(e) => debug!("Dropping malformed attitude frame: {}", e),
                     },
-                    _ => {}
+                    Ok(Err(e)) => debug!("Attitude receive failed: {}", e),
+                    Err(_) => {
+                        counters.timeouts.fetch_add(1, Ordering::Relaxed);
+                    }
                 }
 
                 // maintain Frequency
@@ -182,157 +986,2762 @@ impl A8Mini {
             }
         });
 
-        rx
+        (rx, stats)
     }
 
-    pub async fn get_firmware_version(&self) -> anyhow::Result<control::A8MiniFirmwareVersion> {
-        let response = self
-            .send_command(control::A8MiniSimpleCommand::FirmwareVersionInformation)
-            .await?;
+    /// Reads the attitude, then keeps polling it at `rate_hz` in the background, publishing each
+    /// value to the returned receiver. Clone the receiver to share one poller between any number
+    /// of subscribers; each sees the latest value immediately. Polling stops once every
+    /// receiver is dropped. Failed polls are skipped, keeping the last value.
+    pub async fn spawn_attitude_watch(&self, rate_hz: u32) -> anyhow::Result<watch::Receiver<control::A8MiniAttitude>> {
+        let (tx, rx) = watch::channel(self.get_attitude_information().await?);
+        let command_socket = self.command_socket.clone();
+        let command_lock = self.command_lock.clone();
+        let sequence = self.sequence.clone();
+        let recv_timeout = self.config.recv_timeout;
+        let crc_init = self.crc_init;
+        let invert_attitude = self.invert_attitude;
+        let period = Duration::from_secs_f64(1.0 / rate_hz.max(1) as f64);
 
-        // Header is 8 bytes. Payload is 8 bytes. Total packet should be at least 16 bytes.
-        if response.len() < 16 {
-             return Err(anyhow::anyhow!("Response too short for firmware version"));
-        }
-        
-        // Slice the payload (Indices 8 to 16)
-        let data_slice = &response[8..16];
-        let version_info: control::A8MiniFirmwareVersion = deserialize(data_slice)?;
-        
-        Ok(version_info)
-    }
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = tx.closed() => break,
+                    _ = ticker.tick() => {}
+                }
+                match request_attitude(&command_socket, &command_lock, &sequence, recv_timeout, crc_init).await {
+                    Ok((attitude, _)) => {
+                        tx.send_replace(if invert_attitude { attitude.inverted() } else { attitude });
+                    }
+                    Err(e) => debug!("Attitude poll failed: {}", e),
+                }
+            }
+            debug!("All attitude subscribers dropped, stopping poller.");
+        });
 
-    /// Sends a `control::HTTPQuery` and returns the corresponding received `control::HTTPResponse`.
-    pub async fn send_http_query<T: control::HTTPQuery>(
-        &self,
-        query: T,
-    ) -> anyhow::Result<control::HTTPResponse> {
-        let response = reqwest::get(query.to_string()).await?;
-        debug!("Waiting for HTTP response.");
+        Ok(rx)
+    }
 
-        let json = response.json::<control::HTTPResponse>().await?;
-        debug!("Received HTTP response.");
-        Ok(json)
+    /// Retrieves the highest zoom factor the camera supports.
+    pub async fn get_max_zoom(&self) -> anyhow::Result<f32> {
+        let frame = self.send_command(control::A8MiniSimpleCommand::MaxZoomInformation).await?;
+        Ok(control::zoom_from_payload(&frame.payload)?)
     }
 
-    
+    /// Retrieves the current zoom factor.
+    pub async fn get_zoom(&self) -> anyhow::Result<f32> {
+        let frame = self.send_command(control::A8MiniComplexCommand::GetZoomLevel).await?;
+        Ok(control::zoom_from_payload(&frame.payload)?)
+    }
 
-    /// Retrieves an image or video (WIP) from the camera.
-    pub async fn send_http_media_query<T: control::HTTPQuery>(
-        &self,
-        query: T,
-    ) -> anyhow::Result<Vec<u8>> {
-        let response = reqwest::get(query.to_string()).await?;
-        info!("Waiting for HTTP response.");
+    /// Zooms to `factor`, rounded to a tenth, and returns the zoom factor read back afterwards.
+    /// Fails with `A8MiniError::OutOfRange` outside 1.0 to the camera's maximum zoom.
+    pub async fn zoom_to(&self, factor: f32) -> anyhow::Result<f32> {
+        let max_zoom = self.get_max_zoom().await?;
+        let command = control::A8MiniComplexCommand::absolute_zoom(factor, max_zoom)?;
+        self.send_command(command).await?;
 
-        let image_bytes = response.bytes().await?;
-        info!("Received HTTP response.");
-        Ok(image_bytes.to_vec())
+        let zoom = self.get_zoom().await?;
+        if (zoom - factor).abs() > 0.1 {
+            warn!("Requested {:.1}x zoom, camera reports {:.1}x.", factor, zoom);
+        }
+        Ok(zoom)
     }
 
-    
-}
+    /// Retrieves the gimbal's configuration: HDR, recording state, motion mode, mounting and output.
+    pub async fn get_gimbal_status(&self) -> anyhow::Result<control::GimbalStatus> {
+        let frame = self.send_command(control::A8MiniSimpleCommand::GimbalStatus).await?;
+        Ok(control::GimbalStatus::from_payload(&frame.payload)?)
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Identifies the camera model from its hardware ID, and reads its HDR state and stream
+    /// resolutions. Unrecognized models come back as `CameraModel::Unknown` rather than an error.
+    /// The model is kept for checking model-specific commands; see `set_model`.
+    pub async fn get_camera_info(&self) -> anyhow::Result<control::CameraInfo> {
+        let hardware_id = self.send_command(control::A8MiniSimpleCommand::HardwareIDInformation).await?;
+        let status = self.send_command(control::A8MiniSimpleCommand::CameraInformation).await?;
+        let status = control::GimbalStatus::from_payload(&status.payload)?;
+        let main_stream = self.get_codec_specs(1).await?;
+        let sub_stream = self.get_codec_specs(2).await?;
+
+        let model = control::CameraModel::from_hardware_id(&hardware_id.payload);
+        self.set_model(Some(model.clone()));
+
+        Ok(control::CameraInfo {
+            model,
+            hardware_id: control::hardware_id_from_payload(&hardware_id.payload),
+            hdr: status.hdr == 1,
+            main_stream_resolution: main_stream.resolution(),
+            sub_stream_resolution: sub_stream.resolution(),
+        })
+    }
 
-    use std::thread::sleep;
-    use std::time::Duration;
-    use tokio::fs::File;
-    use tokio::io::AsyncWriteExt;
+    /// Switches the gimbal's motion mode.
+    pub async fn set_mode(&self, mode: control::GimbalMode) -> anyhow::Result<()> {
+        self.send_command_blind(mode.command()).await
+    }
 
+    /// Reads how the gimbal is mounted from its status.
+    pub async fn mounting_direction(&self) -> anyhow::Result<control::MountingDirection> {
+        let status = self.get_gimbal_status().await?;
+        status
+            .mounting()
+            .ok_or_else(|| anyhow!("Unknown mounting direction {}", status.mounting_direction))
+    }
 
-    #[ignore]
-    #[tokio::test]
-    async fn test_take_and_download_photo() -> anyhow::Result<()> {
-        let cam: A8Mini = A8Mini::connect().await?;
+    /// Reads the gimbal's motion mode back from its status.
+    pub async fn get_mode(&self) -> anyhow::Result<control::GimbalMode> {
+        let status = self.get_gimbal_status().await?;
+        status
+            .mode()
+            .ok_or_else(|| anyhow!("Unknown gimbal motion mode {}", status.motion_mode))
+    }
 
-        cam.send_command(control::A8MiniSimpleCommand::TakePicture)
-            .await?;
-        sleep(Duration::from_millis(500));
-        let num_pictures = cam
-            .send_http_query(control::A8MiniSimpleHTTPQuery::GetMediaCountPhotos)
-            .await?
-            .data
-            .count
-            .unwrap();
-        let picture_bytes = cam
-            .send_http_media_query(control::A8MiniComplexHTTPQuery::GetPhoto(num_pictures as u32))
-            .await?;
-        File::create("tmp.jpeg")
-            .await?
-            .write_all(&picture_bytes)
+    /// Queries the camera, gimbal and zoom firmware versions. The result is cached for
+    /// `firmware_at_least`.
+    pub async fn get_firmware_version(&self) -> anyhow::Result<control::A8MiniFirmwareVersion> {
+        let frame = self
+            .send_command(control::A8MiniSimpleCommand::FirmwareVersionInformation)
             .await?;
+        let version = control::A8MiniFirmwareVersion::from_payload(&frame.payload)?;
+        *self.firmware.write().unwrap() = Some(version);
+        Ok(version)
+    }
 
-        Ok(())
+    /// Whether the gimbal firmware is at least `major.minor.patch`, e.g. to check a command is
+    /// implemented before sending it. Queries the version only if it isn't cached yet.
+    pub async fn firmware_at_least(&self, major: u8, minor: u8, patch: u8) -> anyhow::Result<bool> {
+        let cached = *self.firmware.read().unwrap();
+        let version = match cached {
+            Some(version) => version,
+            None => self.get_firmware_version().await?,
+        };
+        Ok(version.gimbal_version() >= (major, minor, patch))
     }
 
-    #[ignore]
-    #[tokio::test]
-    async fn test_send_simple_commands_blind() -> anyhow::Result<()> {
-        let cam: A8Mini = A8Mini::connect().await?;
+    /// Builds the full URL of a `control::HTTPQuery` against the connected camera and its
+    /// media API path.
+    pub fn http_url<T: control::HTTPQuery>(&self, query: &T) -> String {
+        format!("{}{}", self.http_base_url, query.path_with_api_path(&self.media_api_path))
+    }
 
-        cam.send_command_blind(control::A8MiniSimpleCommand::RotateLeft).await?;
-        sleep(Duration::from_millis(500));
+    /// Retrieves the video encoding parameters of a stream (0: recording, 1: main, 2: sub).
+    pub async fn get_codec_specs(&self, stream_type: u8) -> anyhow::Result<control::CodecSpecs> {
+        let frame = self
+            .send_command(control::A8MiniComplexCommand::GetCodecSpecs(stream_type))
+            .await?;
+        Ok(control::CodecSpecs::from_payload(&frame.payload)?)
+    }
 
-        cam.send_command_blind(control::A8MiniSimpleCommand::RotateRight).await?;
-        sleep(Duration::from_millis(1000));
+    /// Validates and applies a `control::CodecConfig`, failing with
+    /// `A8MiniError::UnsupportedCodecConfig` before anything is sent if the camera can't use it.
+    pub async fn set_codec_specs(&self, config: control::CodecConfig) -> anyhow::Result<()> {
+        self.send_command(config.to_command()?)
+            .await
+            .with_context(|| format!("failed to apply codec specs {:?}", config))?;
 
-        cam.send_command_blind(control::A8MiniSimpleCommand::RotateLeft).await?;
-        sleep(Duration::from_millis(500));
+        Ok(())
+    }
 
-        cam.send_command_blind(control::A8MiniSimpleCommand::StopRotation).await?;
+    /// Writes `control::CodecConfig::factory_defaults` to all three streams, undoing
+    /// `set_codec_specs` experiments. Only the recording stream's values are SIYI's documented
+    /// default; the main and sub stream values are assumed. Stops at the first stream the camera
+    /// rejects.
+    pub async fn reset_codec_defaults(&self) -> anyhow::Result<()> {
+        for config in control::CodecConfig::factory_defaults() {
+            self.set_codec_specs(config).await?;
+        }
+        Ok(())
+    }
+
+    /// Changes the recording resolution, keeping the current codec and bitrate.
+    pub async fn set_resolution(&self, resolution: control::Resolution) -> anyhow::Result<()> {
+        let specs = self.get_codec_specs(0).await?;
+        let codec = specs
+            .video_codec()
+            .ok_or_else(|| anyhow!("Unknown codec {} on recording stream", specs.codec))?;
+        let config = control::CodecConfig::uhd_4k(specs.bitrate_kbps)
+            .codec(codec)
+            .resolution(resolution);
+        self.set_codec_specs(config).await
+    }
+
+    /// Reads the recording resolution back from the codec specs.
+    pub async fn get_resolution(&self) -> anyhow::Result<control::Resolution> {
+        let specs = self.get_codec_specs(0).await?;
+        specs
+            .resolution()
+            .ok_or_else(|| anyhow!("Unsupported resolution {}x{}", specs.width, specs.height))
+    }
+
+    /// Retrieves the number of photos stored on the SD card.
+    pub async fn get_photo_information(&self) -> anyhow::Result<i32> {
+        self.media_count(control::MediaKind::Photo).await
+    }
+
+    /// Retrieves the number of videos stored on the SD card.
+    pub async fn get_video_count(&self) -> anyhow::Result<i32> {
+        self.media_count(control::MediaKind::Video).await
+    }
+
+    /// Retrieves the number of files of `kind` stored on the SD card.
+    /// Fails with `A8MiniError::MalformedResponse` if the camera omits the count.
+    pub async fn media_count(&self, kind: control::MediaKind) -> anyhow::Result<i32> {
+        Ok(self.send_http_query(control::MediaCountQuery(kind)).await?.data.count)
+    }
+
+    /// Reads whether an SD card is inserted and how many files it holds. Counts the HTTP API
+    /// doesn't return are `None`. The HTTP API has no storage endpoint, so there is no free or
+    /// total space to report; card presence comes from `get_gimbal_status`.
+    pub async fn get_storage_info(&self) -> anyhow::Result<control::StorageInfo> {
+        let (status, photos, videos) = tokio::join!(
+            self.get_gimbal_status(),
+            self.get_photo_information(),
+            self.get_video_count()
+        );
+        Ok(storage_info(&status?, &photos, &videos))
+    }
+
+    /// Reads gimbal status, attitude, storage and firmware concurrently, for a dashboard to
+    /// poll. A failed query leaves its field `None`; only if every query fails does this fail,
+    /// with `A8MiniError::NoCameraFound`.
+    pub async fn health(&self) -> Result<control::CameraHealth, A8MiniError> {
+        let (status, attitude, photos, videos, firmware) = tokio::join!(
+            self.get_gimbal_status(),
+            self.get_attitude_information(),
+            self.get_photo_information(),
+            self.get_video_count(),
+            self.get_firmware_version()
+        );
+
+        let answered = [status.is_ok(), attitude.is_ok(), photos.is_ok(), videos.is_ok(), firmware.is_ok()];
+        if !answered.contains(&true) {
+            let address = self.command_socket.peer_addr().map(|a| a.to_string()).unwrap_or_default();
+            return Err(A8MiniError::NoCameraFound(address));
+        }
+
+        Ok(control::CameraHealth {
+            storage: status.as_ref().ok().map(|status| storage_info(status, &photos, &videos)),
+            gimbal_status: status.inspect_err(|e| warn!("Gimbal status unavailable: {}", e)).ok(),
+            attitude: attitude.inspect_err(|e| warn!("Attitude unavailable: {}", e)).ok(),
+            firmware: firmware.inspect_err(|e| warn!("Firmware version unavailable: {}", e)).ok(),
+        })
+    }
+
+    /// Lists every file of `kind` on the SD card.
+    pub async fn list_media(&self, kind: control::MediaKind) -> anyhow::Result<Vec<control::MediaEntry>> {
+        let count = self.media_count(kind).await?;
+        let query = control::MediaListQuery { kind, start: 0, count: count.max(0) as u32 };
+        Ok(self.send_http_query(query).await?.data.list)
+    }
+
+    /// Downloads every file of `kind` into `dest_dir`, at most `concurrency` at a time. Files that
+    /// already exist there with the listed size are skipped. A failed file doesn't stop the
+    /// others; failures are collected in the returned summary.
+    pub async fn download_all(
+        &self,
+        kind: control::MediaKind,
+        dest_dir: &Path,
+        concurrency: usize,
+    ) -> anyhow::Result<DownloadSummary> {
+        let entries = self.list_media(kind).await?;
+        info!("Downloading {} files into {}.", entries.len(), dest_dir.display());
+
+        let results: Vec<_> = stream::iter(entries)
+            .map(|entry| async move {
+                let result = self.download_entry(kind, &entry, dest_dir).await;
+                (entry.name, result)
+            })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await;
+
+        let mut summary = DownloadSummary::default();
+        for (name, result) in results {
+            match result {
+                Ok((path, true)) => summary.downloaded.push(path),
+                Ok((path, false)) => summary.skipped.push(path),
+                Err(e) => {
+                    warn!("Failed to download {}: {}", name, e);
+                    summary.failed.push((name, e));
+                }
+            }
+        }
+        Ok(summary)
+    }
+
+    /// One file of `download_all`. Returns its local path and whether it was downloaded rather
+    /// than skipped.
+    async fn download_entry(
+        &self,
+        kind: control::MediaKind,
+        entry: &control::MediaEntry,
+        dest_dir: &Path,
+    ) -> anyhow::Result<(PathBuf, bool)> {
+        // never let a listed name escape `dest_dir`
+        let file_name = Path::new(&entry.name)
+            .file_name()
+            .ok_or_else(|| anyhow!("Invalid media file name {:?}", entry.name))?;
+        let path = dest_dir.join(file_name);
+
+        if let (Some(size), Ok(metadata)) = (entry.size, tokio::fs::metadata(&path).await) {
+            if metadata.len() == size {
+                debug!("Skipping {}, already downloaded.", path.display());
+                return Ok((path, false));
+            }
+        }
+
+        let mut file = tokio::fs::File::create(&path).await?;
+        self.download_media_to(kind.file_query(entry.name.as_str()), &mut file).await?;
+        Ok((path, true))
+    }
+
+    /// Downloads the newest photo on the SD card. Fails with `A8MiniError::NoMedia` if there
+    /// are none.
+    pub async fn get_latest_photo(&self) -> anyhow::Result<Vec<u8>> {
+        self.get_latest_media(control::MediaKind::Photo).await
+    }
+
+    /// Downloads the newest video on the SD card. Fails with `A8MiniError::NoMedia` if there
+    /// are none.
+    pub async fn get_latest_video(&self) -> anyhow::Result<Vec<u8>> {
+        self.get_latest_media(control::MediaKind::Video).await
+    }
+
+    /// Downloads the highest-numbered file of `kind`, whose index is the media count.
+    async fn get_latest_media(&self, kind: control::MediaKind) -> anyhow::Result<Vec<u8>> {
+        let count = self.media_count(kind).await?;
+        if count <= 0 {
+            return Err(A8MiniError::NoMedia(kind).into());
+        }
+        self.send_http_media_query(control::A8MiniComplexHTTPQuery::GetMedia(kind, count as u32))
+            .await
+    }
+
+    /// Takes a picture, waits for it to be written to the SD card and downloads it.
+    pub async fn take_photo_and_download(&self) -> anyhow::Result<Vec<u8>> {
+        let baseline = self.get_photo_information().await?;
+        self.send_command_blind(control::A8MiniSimpleCommand::TakePicture).await?;
+        let count = self
+            .wait_for_photo_count_increase(baseline, constants::CAPTURE_TIMEOUT)
+            .await?;
+
+        self.send_http_media_query(control::A8MiniComplexHTTPQuery::GetMedia(control::MediaKind::Photo, count as u32))
+            .await
+    }
+
+    /// Polls the photo count until it exceeds `baseline`, returning the new count. Confirms a
+    /// capture instead of sleeping a fixed time, which slow SD cards can overrun.
+    /// Fails with `A8MiniError::Timeout` if the count never rises, i.e. the capture failed.
+    async fn wait_for_photo_count_increase(
+        &self,
+        baseline: i32,
+        timeout: Duration,
+    ) -> anyhow::Result<i32> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let count = self.get_photo_information().await?;
+            if count > baseline {
+                return Ok(count);
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(A8MiniError::Timeout("photo count to increase").into());
+            }
+            tokio::time::sleep(constants::CAPTURE_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Starts recording, unless the camera already is, and returns a guard that stops it when
+    /// dropped, including while unwinding from a panic.
+    pub async fn start_recording(&self) -> anyhow::Result<RecordingGuard<'_>> {
+        if self.get_gimbal_status().await?.record_status != 1 {
+            self.send_command_blind(control::A8MiniSimpleCommand::RecordVideo).await?;
+            *self.recording_since.write().unwrap() = Some(tokio::time::Instant::now());
+        }
+        Ok(RecordingGuard { camera: self, armed: true })
+    }
+
+    /// Reads whether the camera is recording. The camera doesn't report the recording's length,
+    /// so `elapsed_secs` counts from `start_recording`, or from the first call that saw the
+    /// recording running if it was started elsewhere.
+    pub async fn get_recording_state(&self) -> anyhow::Result<control::RecordingState> {
+        let active = self.get_gimbal_status().await?.record_status == 1;
+        let mut recording_since = self.recording_since.write().unwrap();
+        if !active {
+            *recording_since = None;
+            return Ok(control::RecordingState { active: false, elapsed_secs: 0 });
+        }
+
+        let since = *recording_since.get_or_insert_with(tokio::time::Instant::now);
+        Ok(control::RecordingState { active: true, elapsed_secs: since.elapsed().as_secs() as u32 })
+    }
+
+    /// Sets the camera clock to the current system time.
+    pub async fn sync_time_now(&self) -> anyhow::Result<()> {
+        self.send_command(control::A8MiniComplexCommand::SetTimeUTC(std::time::SystemTime::now()))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Sends a `control::HTTPQuery` and returns the received `control::HTTPResponse`, its `data`
+    /// decoded as the query's `Response`. Transient failures are retried as configured with `A8MiniBuilder::http_retry`.
+    #[instrument(level = "debug", skip(self))]
+    pub async fn send_http_query<T: control::HTTPQuery>(
+        &self,
+        query: T,
+    ) -> anyhow::Result<control::HTTPResponse<T::Response>> {
+        let url = self.http_url(&query);
+        let mut delay = self.http_retry_delay;
+
+        for attempt in 1..self.http_attempts {
+            match self.try_http_query(&url).await {
+                Err(e) if is_transient(&e) => {
+                    warn!("HTTP attempt {}/{} failed, retrying in {:?}: {}", attempt, self.http_attempts, delay, e);
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                }
+                result => return result,
+            }
+        }
+
+        self.try_http_query(&url).await
+    }
+
+    /// One attempt of `send_http_query`.
+    async fn try_http_query<D: serde::de::DeserializeOwned>(&self, url: &str) -> anyhow::Result<control::HTTPResponse<D>> {
+        let requested_at = tokio::time::Instant::now();
+        trace!("GET {}", url);
+        let response = self.http_client.get(url).send().await?.error_for_status()?;
+        debug!("Waiting for HTTP response.");
+
+        let body = response.bytes().await?;
+        debug!("Received HTTP response after {:?}.", requested_at.elapsed());
+        Ok(control::HTTPResponse::from_body(url, &body)?)
+    }
+
+    /// Retrieves an image or video (WIP) from the camera. Fails on an HTTP error status, e.g. 404
+    /// for a missing file, instead of returning the error page as media.
+    #[instrument(level = "debug", skip(self))]
+    pub async fn send_http_media_query<T: control::HTTPQuery>(
+        &self,
+        query: T,
+    ) -> anyhow::Result<Vec<u8>> {
+        let url = self.http_url(&query);
+        let requested_at = tokio::time::Instant::now();
+        trace!("GET {}", url);
+        let response = self.http_client.get(url).send().await?.error_for_status()?;
+        info!("Waiting for HTTP response.");
+
+        let image_bytes = response.bytes().await?;
+        info!(
+            "Received HTTP response of {} bytes after {:?}.",
+            image_bytes.len(),
+            requested_at.elapsed()
+        );
+        Ok(image_bytes.to_vec())
+    }
+
+    /// Streams an image or video from the camera into `writer` chunk by chunk, without buffering
+    /// the whole file in memory. Returns the number of bytes written.
+    pub async fn download_media_to<T: control::HTTPQuery, W: AsyncWrite + Unpin>(
+        &self,
+        query: T,
+        writer: &mut W,
+    ) -> anyhow::Result<u64> {
+        self.download_media_with_progress(query, writer, |_, _| {}).await
+    }
+
+    /// Same as `download_media_to`, calling `on_progress(bytes_so_far, content_length)` once the
+    /// response headers arrive and again after every received chunk. `content_length` is `None`
+    /// when the camera doesn't send a `Content-Length`.
+    #[instrument(level = "debug", skip(self, writer, on_progress))]
+    pub async fn download_media_with_progress<T: control::HTTPQuery, W: AsyncWrite + Unpin>(
+        &self,
+        query: T,
+        writer: &mut W,
+        on_progress: impl FnMut(u64, Option<u64>),
+    ) -> anyhow::Result<u64> {
+        self.stream_media(query, writer, on_progress, std::future::pending()).await
+    }
+
+    /// Same as `download_media_to`, abandoning the transfer with `A8MiniError::Cancelled` as soon
+    /// as `cancel` completes, e.g. `token.cancelled()` or a oneshot receiver. The connection is
+    /// closed, and every chunk received before the cancel is written and flushed to `writer`,
+    /// so a partial file ends cleanly at a chunk boundary for the caller to truncate or keep.
+    pub async fn download_media_cancellable<T: control::HTTPQuery, W: AsyncWrite + Unpin>(
+        &self,
+        query: T,
+        writer: &mut W,
+        cancel: impl std::future::Future<Output = ()>,
+    ) -> anyhow::Result<u64> {
+        self.stream_media(query, writer, |_, _| {}, cancel).await
+    }
+
+    /// Shared body of `download_media_with_progress` and `download_media_cancellable`.
+    async fn stream_media<T: control::HTTPQuery, W: AsyncWrite + Unpin>(
+        &self,
+        query: T,
+        writer: &mut W,
+        mut on_progress: impl FnMut(u64, Option<u64>),
+        cancel: impl std::future::Future<Output = ()>,
+    ) -> anyhow::Result<u64> {
+        let mut cancel = std::pin::pin!(cancel);
+        let url = self.http_url(&query);
+        trace!("GET {}", url);
+        let mut response = tokio::select! {
+            biased;
+            _ = &mut cancel => return Err(A8MiniError::Cancelled.into()),
+            response = self.http_client.get(url).send() => response?.error_for_status()?,
+        };
+        info!("Streaming HTTP media response.");
+
+        let content_length = response.content_length();
+        let mut written: u64 = 0;
+        on_progress(written, content_length);
+
+        loop {
+            let chunk = tokio::select! {
+                biased;
+                _ = &mut cancel => {
+                    writer.flush().await?;
+                    info!("Media download cancelled after {} bytes.", written);
+                    return Err(A8MiniError::Cancelled.into());
+                }
+                chunk = next_chunk(&mut response, constants::MEDIA_IDLE_TIMEOUT) => chunk?,
+            };
+            let Some(chunk) = chunk else { break };
+            writer.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+            on_progress(written, content_length);
+        }
+        writer.flush().await?;
+
+        info!("Received HTTP media response of {} bytes.", written);
+        Ok(written)
+    }
+
+    /// Same as `download_media_to`, restarting a failed or stalled transfer up to `attempts`
+    /// times in total (0 counts as 1). A transfer stalls when the response headers or the next
+    /// data take longer than `idle_timeout`. Restarts resume from the last received byte with a
+    /// `Range` request; if the camera answers with the whole file instead, `writer` is rewound
+    /// and the file downloaded again from the start.
+    #[instrument(level = "debug", skip(self, writer))]
+    pub async fn download_media_with_retry<T: control::HTTPQuery, W: AsyncWrite + AsyncSeek + Unpin>(
+        &self,
+        query: T,
+        writer: &mut W,
+        attempts: u32,
+        idle_timeout: Duration,
+    ) -> anyhow::Result<u64> {
+        let url = self.http_url(&query);
+        let attempts = attempts.max(1);
+        let mut written: u64 = 0;
+
+        for attempt in 1..=attempts {
+            let result = self
+                .resume_download(&url, writer, &mut written, idle_timeout)
+                .await;
+            match result {
+                Ok(()) => {
+                    info!("Received HTTP media response of {} bytes.", written);
+                    return Ok(written);
+                }
+                Err(e) if attempt < attempts => {
+                    warn!("Download attempt {}/{} failed after {} bytes: {}", attempt, attempts, written, e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(A8MiniError::Timeout("media download").into())
+    }
+
+    /// One attempt of `download_media_with_retry`, requesting the bytes after `written`.
+    async fn resume_download<W: AsyncWrite + AsyncSeek + Unpin>(
+        &self,
+        url: &str,
+        writer: &mut W,
+        written: &mut u64,
+        idle_timeout: Duration,
+    ) -> anyhow::Result<()> {
+        let mut request = self.http_client.get(url);
+        if *written > 0 {
+            request = request.header(reqwest::header::RANGE, format!("bytes={}-", written));
+        }
+        let mut response = tokio::time::timeout(idle_timeout, request.send())
+            .await
+            .map_err(|_| A8MiniError::Timeout("media response"))??
+            .error_for_status()?;
+
+        if *written > 0 && response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+            debug!("Range not honored, restarting download from the start.");
+            writer.seek(SeekFrom::Start(0)).await?;
+            *written = 0;
+        }
+
+        while let Some(chunk) = next_chunk(&mut response, idle_timeout).await? {
+            writer.write_all(&chunk).await?;
+            *written += chunk.len() as u64;
+        }
+        writer.flush().await?;
+
+        Ok(())
+    }
+}
+
+/// `StorageInfo` from the queries `A8Mini::get_storage_info` and `A8Mini::health` share. Failed
+/// counts are logged and left `None`.
+fn storage_info(
+    status: &control::GimbalStatus,
+    photos: &anyhow::Result<i32>,
+    videos: &anyhow::Result<i32>,
+) -> control::StorageInfo {
+    control::StorageInfo {
+        sd_card_present: status.sd_card_present(),
+        photo_count: photos.as_ref().inspect_err(|e| warn!("Photo count unavailable: {}", e)).ok().copied(),
+        video_count: videos.as_ref().inspect_err(|e| warn!("Video count unavailable: {}", e)).ok().copied(),
+    }
+}
+
+/// Turns "no route" socket errors into `A8MiniError::NetworkUnreachable`, as UDP `connect`
+/// succeeds without any packet reaching `address`. Other errors are passed through.
+fn unreachable_or(e: std::io::Error, address: &str) -> anyhow::Error {
+    match e.kind() {
+        std::io::ErrorKind::NetworkUnreachable | std::io::ErrorKind::HostUnreachable => {
+            A8MiniError::NetworkUnreachable(address.to_string()).into()
+        }
+        _ => e.into(),
+    }
+}
+
+/// Probes `target` for `A8Mini::discover`. Split out so tests can probe a loopback mock.
+async fn discover_at(target: SocketAddr, timeout: Duration) -> Result<Vec<IpAddr>, A8MiniError> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await?;
+    let probe = control::Command::to_bytes(&control::A8MiniSimpleCommand::FirmwareVersionInformation);
+    if let Err(e) = socket.set_broadcast(true) {
+        warn!("Cannot enable broadcast, discovering no cameras: {}", e);
+        return Ok(Vec::new());
+    }
+    if let Err(e) = socket.send_to(&probe, target).await {
+        warn!("Discovery probe to {} failed, discovering no cameras: {}", target, e);
+        return Ok(Vec::new());
+    }
+
+    let mut cameras = Vec::new();
+    let mut recv_buffer = [0; constants::RECV_BUFF_SIZE];
+    let deadline = tokio::time::Instant::now() + timeout;
+    while let Ok(received) = tokio::time::timeout_at(deadline, socket.recv_from(&mut recv_buffer)).await {
+        let (recv_len, from) = received?;
+        match control::AckFrame::validate(&recv_buffer[..recv_len]) {
+            Ok((0x01, _)) if !cameras.contains(&from.ip()) => {
+                info!("Discovered camera at {}.", from.ip());
+                cameras.push(from.ip());
+            }
+            _ => trace!("Ignoring datagram from {} during discovery.", from),
+        }
+    }
+    Ok(cameras)
+}
+
+/// `AttitudeInformation` frame, built once for `request_attitude`, which stamps each request's
+/// sequence number into a copy.
+const ATTITUDE_REQUEST: [u8; 10] = [0x55, 0x66, 0x01, 0x00, 0x00, 0x00, 0x00, 0x0d, 0xe8, 0x05];
+
+/// Requests the attitude and decodes the ACK straight from the receive buffer, holding
+/// `command_lock` throughout like `A8Mini::send_command` so no ACK is stolen. Also returns the
+/// time from the send to the ACK. Shared by `A8Mini::poll_attitude`, `A8Mini::ping_latency` and
+/// the `spawn_attitude_watch` task.
+async fn request_attitude(
+    command_socket: &UdpSocket,
+    command_lock: &Mutex<()>,
+    next_sequence: &AtomicU16,
+    recv_timeout: Duration,
+    crc_init: u16,
+) -> Result<(control::A8MiniAttitude, Duration), A8MiniError> {
+    let mut request = ATTITUDE_REQUEST;
+    let sequence = next_sequence.fetch_add(1, Ordering::Relaxed);
+    control::stamp_sequence(&mut request, sequence)?;
+    seal(&mut request, crc_init);
+
+    let _exchange = command_lock.lock().await;
+    let sent_at = tokio::time::Instant::now();
+    let sent = command_socket.send(&request).await?;
+    check_send_len(sent, request.len())?;
+
+    let mut recv_buffer = [0; constants::RECV_BUFF_SIZE];
+    let deadline = tokio::time::Instant::now() + recv_timeout;
+    loop {
+        let recv_len = tokio::time::timeout_at(deadline, command_socket.recv(&mut recv_buffer))
+            .await
+            .map_err(|_| A8MiniError::Timeout("attitude ACK"))??;
+        // like `A8Mini::exchange_frame`, skip late ACKs of earlier, timed-out requests
+        if let Ok((0x0D, payload)) = control::AckFrame::validate_with_crc_init(&recv_buffer[..recv_len], crc_init) {
+            if control::bytes::read_u16_le(&recv_buffer, 5) == sequence {
+                return Ok((control::A8MiniAttitude::from_payload(payload)?, sent_at.elapsed()));
+            }
+        }
+    }
+}
+
+/// Recomputes the CRC of a frame built with `constants::CRC16_INIT` if `crc_init` differs. Shared
+/// by `A8Mini::seal` and the background tasks that send without the handle.
+fn seal(frame_bytes: &mut [u8], crc_init: u16) {
+    if crc_init != constants::CRC16_INIT {
+        control::stamp_crc(frame_bytes, crc_init);
+    }
+}
+
+/// Takes `command_lock` for a blind send once `min_interval` has passed since the previous one
+/// (see `A8MiniBuilder::min_command_interval`), and records the send. The wait happens before
+/// taking the lock, so ACKed exchanges and heartbeats aren't held up behind the limiter.
+async fn lock_for_blind_send<'a>(
+    command_lock: &'a Mutex<()>,
+    last_blind_send: &std::sync::Mutex<Option<tokio::time::Instant>>,
+    min_interval: Option<Duration>,
+) -> tokio::sync::MutexGuard<'a, ()> {
+    let Some(interval) = min_interval else {
+        return command_lock.lock().await;
+    };
+    loop {
+        let last_send = *last_blind_send.lock().unwrap();
+        if let Some(last_send) = last_send {
+            tokio::time::sleep_until(last_send + interval).await;
+        }
+
+        let exchange = command_lock.lock().await;
+        let mut last_send = last_blind_send.lock().unwrap();
+        // another blind send may have gone out while this one waited for the lock
+        if last_send.is_some_and(|last_send| last_send + interval > tokio::time::Instant::now()) {
+            continue;
+        }
+        *last_send = Some(tokio::time::Instant::now());
+        return exchange;
+    }
+}
+
+/// Whether an HTTP failure may go away on its own: a dropped or timed-out connection or a 5xx.
+fn is_transient(e: &anyhow::Error) -> bool {
+    let Some(e) = e.downcast_ref::<reqwest::Error>() else {
+        return false;
+    };
+    match e.status() {
+        Some(status) => status.is_server_error(),
+        None => e.is_connect() || e.is_timeout() || e.is_request() || e.is_body(),
+    }
+}
+
+/// The sending side of the command socket. Only `UdpSocket` implements it outside tests, which
+/// stand in sockets that report short writes, as embedded stacks may.
+trait SendDatagram {
+    async fn send(&self, buf: &[u8]) -> std::io::Result<usize>;
+    fn try_send(&self, buf: &[u8]) -> std::io::Result<usize>;
+    fn peer_addr(&self) -> std::io::Result<SocketAddr>;
+}
+
+impl SendDatagram for UdpSocket {
+    async fn send(&self, buf: &[u8]) -> std::io::Result<usize> {
+        UdpSocket::send(self, buf).await
+    }
+
+    fn try_send(&self, buf: &[u8]) -> std::io::Result<usize> {
+        UdpSocket::try_send(self, buf)
+    }
+
+    fn peer_addr(&self) -> std::io::Result<SocketAddr> {
+        UdpSocket::peer_addr(self)
+    }
+}
+
+/// Sends `frame` whole, failing with `A8MiniError::PartialSend` on a short write.
+async fn send_datagram(socket: &impl SendDatagram, frame: &[u8]) -> anyhow::Result<()> {
+    let sent = socket.send(frame).await.map_err(|e| {
+        let address = socket.peer_addr().map(|a| a.to_string()).unwrap_or_default();
+        unreachable_or(e, &address)
+    })?;
+    Ok(check_send_len(sent, frame.len())?)
+}
+
+/// Non-blocking `send_datagram`. Returns `false`, having sent nothing, if the socket is saturated.
+fn try_send_datagram(socket: &impl SendDatagram, frame: &[u8]) -> anyhow::Result<bool> {
+    match socket.try_send(frame) {
+        Ok(sent) => check_send_len(sent, frame.len()).map(|()| true).map_err(Into::into),
+        Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(false),
+        Err(e) => {
+            let address = socket.peer_addr().map(|a| a.to_string()).unwrap_or_default();
+            Err(unreachable_or(e, &address))
+        }
+    }
+}
+
+/// Fails with `A8MiniError::PartialSend` unless the whole frame went out.
+fn check_send_len(sent: usize, expected: usize) -> Result<(), A8MiniError> {
+    if sent != expected {
+        return Err(A8MiniError::PartialSend { sent, expected });
+    }
+    Ok(())
+}
+
+/// Reads the next body chunk, failing with `A8MiniError::Timeout` if none arrives in `idle_timeout`.
+async fn next_chunk(
+    response: &mut reqwest::Response,
+    idle_timeout: Duration,
+) -> anyhow::Result<Option<bytes::Bytes>> {
+    tokio::time::timeout(idle_timeout, response.chunk())
+        .await
+        .map_err(|_| A8MiniError::Timeout("media data"))?
+        .map_err(Into::into)
+}
+
+/// Recording started by `A8Mini::start_recording`. Stop it with `stop`; dropping the guard
+/// instead sends the stop without waiting.
+///
+/// The stop on drop is best effort: it is a single non-blocking UDP send, so it can be lost on
+/// a congested link and doesn't happen at all if the process is killed or calls `exit`.
+#[derive(Debug)]
+pub struct RecordingGuard<'a> {
+    camera: &'a A8Mini,
+    armed: bool,
+}
+
+impl RecordingGuard<'_> {
+    /// Stops the recording.
+    pub async fn stop(mut self) -> anyhow::Result<()> {
+        self.armed = false;
+        self.camera
+            .send_command_blind(control::A8MiniSimpleCommand::RecordVideo)
+            .await
+    }
+}
+
+/// The camera being recorded on, so status and attitude queries can go through the guard while
+/// recording.
+impl AsRef<A8Mini> for RecordingGuard<'_> {
+    fn as_ref(&self) -> &A8Mini {
+        self.camera
+    }
+}
+
+impl Drop for RecordingGuard<'_> {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        let stop = self.camera.encode(&control::A8MiniSimpleCommand::RecordVideo);
+        match self.camera.command_socket.try_send(&stop) {
+            Ok(_) => info!("Stopped recording on drop."),
+            Err(e) => error!("Failed to stop recording on drop: {}", e),
+        }
+    }
+}
+
+/// Speed control session from `A8Mini::speed_session`. Stop it with `stop`; dropping the guard
+/// instead sends `StopRotation` without waiting. Like `RecordingGuard`'s, the stop on drop is a
+/// single best-effort UDP send.
+#[derive(Debug)]
+pub struct SpeedGuard<'a> {
+    camera: &'a A8Mini,
+    armed: bool,
+}
+
+impl SpeedGuard<'_> {
+    /// Turns the gimbal at raw speeds in `-100..=100`.
+    pub async fn set_speed(&self, yaw: i8, pitch: i8) -> anyhow::Result<()> {
+        self.camera
+            .send_command_blind(control::A8MiniComplexCommand::SetYawPitchSpeed(yaw, pitch))
+            .await
+    }
+
+    /// Same as `A8Mini::set_speed_normalized`.
+    pub async fn set_speed_normalized(&self, yaw: f32, pitch: f32, deadband: f32) -> anyhow::Result<()> {
+        self.camera.set_speed_normalized(yaw, pitch, deadband).await
+    }
+
+    /// Stops the gimbal and ends the session.
+    pub async fn stop(mut self) -> anyhow::Result<()> {
+        self.armed = false;
+        self.camera
+            .send_command_blind(control::A8MiniSimpleCommand::StopRotation)
+            .await
+    }
+}
+
+impl AsRef<A8Mini> for SpeedGuard<'_> {
+    fn as_ref(&self) -> &A8Mini {
+        self.camera
+    }
+}
+
+impl Drop for SpeedGuard<'_> {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        let stop = self.camera.encode(&control::A8MiniSimpleCommand::StopRotation);
+        match self.camera.command_socket.try_send(&stop) {
+            Ok(_) => info!("Stopped gimbal rotation on drop."),
+            Err(e) => error!("Failed to stop gimbal rotation on drop: {}", e),
+        }
+    }
+}
+
+/// Outcome of `A8Mini::download_all`.
+#[derive(Debug, Default)]
+pub struct DownloadSummary {
+    /// Local paths of the files that were downloaded.
+    pub downloaded: Vec<PathBuf>,
+    /// Local paths of the files already present with the right size.
+    pub skipped: Vec<PathBuf>,
+    /// Camera-side names of the files that failed, with the reason.
+    pub failed: Vec<(String, anyhow::Error)>,
+}
+
+/// Counters of `A8Mini::stream_attitude_data_with_stats`, updated by its task as it polls.
+/// Clones share the counters, so they can be read from any task.
+#[derive(Debug, Clone, Default)]
+pub struct AttitudeStats {
+    counters: Arc<AttitudeCounters>,
+}
+
+#[derive(Debug, Default)]
+struct AttitudeCounters {
+    polls_sent: AtomicU64,
+    frames_received: AtomicU64,
+    timeouts: AtomicU64,
+    crc_failures: AtomicU64,
+}
+
+impl AttitudeStats {
+    /// Attitude requests sent.
+    pub fn polls_sent(&self) -> u64 {
+        self.counters.polls_sent.load(Ordering::Relaxed)
+    }
+
+    /// Attitude frames received and decoded.
+    pub fn frames_received(&self) -> u64 {
+        self.counters.frames_received.load(Ordering::Relaxed)
+    }
+
+    /// Polls that got no answer in time.
+    pub fn timeouts(&self) -> u64 {
+        self.counters.timeouts.load(Ordering::Relaxed)
+    }
+
+    /// Frames dropped for a CRC mismatch.
+    pub fn crc_failures(&self) -> u64 {
+        self.counters.crc_failures.load(Ordering::Relaxed)
+    }
+}
+
+/// Handle to a task started by `A8Mini::spawn_heartbeat`. Dropping it stops the heartbeat.
+#[derive(Debug)]
+pub struct HeartbeatHandle {
+    task: JoinHandle<()>,
+}
+
+impl HeartbeatHandle {
+    /// Stops the heartbeat task. Equivalent to dropping the handle.
+    pub fn stop(self) {}
+}
+
+impl Drop for HeartbeatHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::fs::File;
+    use tokio::io::AsyncWriteExt;
+
+    #[test]
+    fn test_unreachable_errors_mapped() {
+        let address = "192.168.144.25:37260";
+        for kind in [std::io::ErrorKind::NetworkUnreachable, std::io::ErrorKind::HostUnreachable] {
+            let err = unreachable_or(kind.into(), address);
+            assert_eq!(
+                err.downcast_ref::<A8MiniError>(),
+                Some(&A8MiniError::NetworkUnreachable(address.to_string()))
+            );
+        }
+
+        let err = unreachable_or(std::io::ErrorKind::ConnectionRefused.into(), address);
+        assert_eq!(err.downcast_ref::<std::io::Error>().map(|e| e.kind()), Some(std::io::ErrorKind::ConnectionRefused));
+    }
+
+    #[test]
+    fn test_check_send_len() {
+        assert_eq!(check_send_len(10, 10), Ok(()));
+        assert_eq!(check_send_len(0, 10), Err(A8MiniError::PartialSend { sent: 0, expected: 10 }));
+        assert_eq!(check_send_len(8, 10), Err(A8MiniError::PartialSend { sent: 8, expected: 10 }));
+    }
+
+    /// Reports every send as `short` bytes shy of the frame.
+    struct ShortSocket {
+        short: usize,
+    }
+
+    impl SendDatagram for ShortSocket {
+        async fn send(&self, buf: &[u8]) -> std::io::Result<usize> {
+            self.try_send(buf)
+        }
+
+        fn try_send(&self, buf: &[u8]) -> std::io::Result<usize> {
+            Ok(buf.len().saturating_sub(self.short))
+        }
+
+        fn peer_addr(&self) -> std::io::Result<SocketAddr> {
+            Ok(SocketAddr::from(([127, 0, 0, 1], 37260)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_short_sends_fail() -> anyhow::Result<()> {
+        let frame = control::Command::to_bytes(&control::A8MiniSimpleCommand::Heartbeat);
+        for short in [1, frame.len()] {
+            let socket = ShortSocket { short };
+            let partial = A8MiniError::PartialSend { sent: frame.len() - short, expected: frame.len() };
+            assert_eq!(send_datagram(&socket, &frame).await.unwrap_err().downcast_ref(), Some(&partial));
+            assert_eq!(try_send_datagram(&socket, &frame).unwrap_err().downcast_ref(), Some(&partial));
+        }
+
+        let whole = ShortSocket { short: 0 };
+        send_datagram(&whole, &frame).await?;
+        assert!(try_send_datagram(&whole, &frame)?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rotate_commands_sent_as_unsigned_bytes() -> anyhow::Result<()> {
+        let mock_socket = tokio::net::UdpSocket::bind("127.0.0.1:0").await?;
+        let mock_port = mock_socket.local_addr()?.port().to_string();
+        let cam = A8Mini::connect_to("127.0.0.1", &mock_port, &mock_port, "0", "0").await?;
+
+        let expected: [(control::A8MiniSimpleCommand, [u8; 12]); 4] = [
+            (
+                control::A8MiniSimpleCommand::RotateUp,
+                [0x55, 0x66, 0x01, 0x02, 0x00, 0x00, 0x00, 0x07, 0x00, 0x2d, 0x3e, 0xd1],
+            ),
+            (
+                control::A8MiniSimpleCommand::RotateDown,
+                [0x55, 0x66, 0x01, 0x02, 0x00, 0x00, 0x00, 0x07, 0x00, 0xd3, 0xef, 0xdf],
+            ),
+            (
+                control::A8MiniSimpleCommand::RotateRight,
+                [0x55, 0x66, 0x01, 0x02, 0x00, 0x00, 0x00, 0x07, 0xd3, 0x00, 0x85, 0x64],
+            ),
+            (
+                control::A8MiniSimpleCommand::RotateLeft,
+                [0x55, 0x66, 0x01, 0x02, 0x00, 0x00, 0x00, 0x07, 0x2d, 0x00, 0x4b, 0x54],
+            ),
+        ];
+
+        let mut recv_buffer = [0u8; constants::RECV_BUFF_SIZE];
+        for (command, expected_bytes) in expected {
+            cam.send_command_blind(command).await?;
+            let recv_len = timeout(constants::RECV_TIMEOUT, mock_socket.recv(&mut recv_buffer)).await??;
+            assert_eq!(&recv_buffer[..recv_len], &expected_bytes, "{:?}", command);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_mock_attitude_round_trip() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().attitude = control::A8MiniAttitude {
+            theta_yaw: -450,
+            theta_pitch: 120,
+            theta_roll: 3,
+            v_yaw: 10,
+            v_pitch: -20,
+            v_roll: 0,
+        };
+        let cam = mock.connect().await?;
+
+        let attitude = cam.get_attitude_information().await?;
+        assert_eq!(attitude, mock.state().attitude);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_firmware_at_least_caches_version() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        // mock gimbal firmware is 0.4.4
+        assert!(cam.firmware_at_least(0, 4, 4).await?);
+        assert!(cam.firmware_at_least(0, 3, 9).await?);
+        assert!(!cam.firmware_at_least(0, 4, 5).await?);
+
+        // answered from the cache, not the now silent camera
+        mock.state().drop_next = u32::MAX;
+        assert!(!cam.firmware_at_least(1, 0, 0).await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_mock_firmware_round_trip() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        let firmware = cam.get_firmware_version().await?;
+        assert_eq!((firmware.code_ver_byte2, firmware.code_ver_byte1, firmware.code_ver_byte0), (3, 2, 1));
+        assert_eq!((firmware.gimbal_ver_byte2, firmware.gimbal_ver_byte1, firmware.gimbal_ver_byte0), (0, 4, 4));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_mock_photo_count_round_trip() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().photo_count = 41;
+        let cam = mock.connect().await?;
+
+        cam.send_command_blind(control::A8MiniSimpleCommand::TakePicture).await?;
+        // no ACK for TakePicture; wait for the mock to count it
+        mock.wait_for(|state| state.photo_count == 42).await?;
+
+        let response = cam
+            .send_http_query(control::A8MiniComplexHTTPQuery::GetMediaCount(control::MediaKind::Photo))
+            .await?;
+        assert!(response.success);
+        assert_eq!(response.data.count, Some(42));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_media_count_per_kind() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().photo_count = 7;
+        mock.state().video_count = 3;
+        let cam = mock.connect().await?;
+
+        assert_eq!(cam.get_photo_information().await?, 7);
+        assert_eq!(cam.get_video_count().await?, 3);
+        assert_eq!(cam.media_count(control::MediaKind::Photo).await?, 7);
+        assert_eq!(cam.media_count(control::MediaKind::Video).await?, 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_download_media_to_streams_whole_file() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let video: Vec<u8> = (0..1_000_000u32).map(|i| i as u8).collect();
+        mock.state()
+            .files
+            .insert("/photo/100SIYI_VID/REC_0001.mp4".to_string(), video.clone());
+        let cam = mock.connect().await?;
+
+        let mut downloaded: Vec<u8> = Vec::new();
+        let written = cam
+            .download_media_to(control::A8MiniComplexHTTPQuery::GetMedia(control::MediaKind::Video, 1), &mut downloaded)
+            .await?;
+
+        assert_eq!(written, video.len() as u64);
+        assert_eq!(downloaded, video);
+
+        // missing files are an error rather than a written 404 body
+        assert!(cam
+            .download_media_to(control::A8MiniComplexHTTPQuery::GetMedia(control::MediaKind::Video, 2), &mut Vec::new())
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_download_media_cancellable() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let video: Vec<u8> = (0..50_000u32).map(|i| (i % 251) as u8).collect();
+        mock.state().files.insert("/photo/100SIYI_VID/REC_0001.mp4".to_string(), video.clone());
+        let cam = mock.connect().await?;
+        let query = control::A8MiniComplexHTTPQuery::GetMedia(control::MediaKind::Video, 1);
+
+        // never cancelled: the whole file
+        let mut downloaded = Vec::new();
+        let written = cam.download_media_cancellable(query.clone(), &mut downloaded, std::future::pending()).await?;
+        assert_eq!(written, video.len() as u64);
+        assert_eq!(downloaded, video);
+
+        // cancelled mid-transfer: what arrived is kept, up to where the camera hung
+        mock.state().stall_after = Some(20_000);
+        let mut partial = Vec::new();
+        let (cancel, cancelled) = tokio::sync::oneshot::channel::<()>();
+        let download = cam.download_media_cancellable(query, &mut partial, async {
+            let _ = cancelled.await;
+        });
+        let trigger = async {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            let _ = cancel.send(());
+        };
+        let (result, ()) = tokio::join!(download, trigger);
+        assert_eq!(result.unwrap_err().downcast_ref(), Some(&A8MiniError::Cancelled));
+        assert_eq!(partial, video[..20_000]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_download_media_with_retry_resumes_with_range() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let video: Vec<u8> = (0..50_000u32).map(|i| (i % 251) as u8).collect();
+        mock.state().files.insert("/photo/100SIYI_VID/REC_0001.mp4".to_string(), video.clone());
+        mock.state().stall_after = Some(20_000);
+        let cam = mock.connect().await?;
+
+        let mut downloaded = std::io::Cursor::new(Vec::new());
+        let written = cam
+            .download_media_with_retry(
+                control::A8MiniComplexHTTPQuery::GetMedia(control::MediaKind::Video, 1),
+                &mut downloaded,
+                2,
+                Duration::from_millis(200),
+            )
+            .await?;
+        assert_eq!(written, video.len() as u64);
+        assert_eq!(downloaded.into_inner(), video);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_download_media_with_retry_restarts_without_range() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let video: Vec<u8> = (0..50_000u32).map(|i| (i % 251) as u8).collect();
+        mock.state().files.insert("/photo/100SIYI_VID/REC_0001.mp4".to_string(), video.clone());
+        mock.state().stall_after = Some(20_000);
+        mock.state().honor_range = false;
+        let cam = mock.connect().await?;
+        let query = control::A8MiniComplexHTTPQuery::GetMedia(control::MediaKind::Video, 1);
+
+        let mut downloaded = std::io::Cursor::new(Vec::new());
+        let written = cam
+            .download_media_with_retry(query.clone(), &mut downloaded, 2, Duration::from_millis(200))
+            .await?;
+        assert_eq!(written, video.len() as u64);
+        assert_eq!(downloaded.into_inner(), video);
+
+        // a single attempt gives up on the stall
+        mock.state().stall_after = Some(20_000);
+        let err = cam
+            .download_media_with_retry(query, &mut std::io::Cursor::new(Vec::new()), 1, Duration::from_millis(200))
+            .await
+            .unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&A8MiniError::Timeout("media data")));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_download_media_with_retry_times_out_waiting_for_headers() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let video: Vec<u8> = (0..50_000u32).map(|i| (i % 251) as u8).collect();
+        mock.state().files.insert("/photo/100SIYI_VID/REC_0001.mp4".to_string(), video.clone());
+        let cam = mock.connect().await?;
+        let query = control::A8MiniComplexHTTPQuery::GetMedia(control::MediaKind::Video, 1);
+
+        mock.state().http_stalls = 1;
+        let err = cam
+            .download_media_with_retry(query.clone(), &mut std::io::Cursor::new(Vec::new()), 1, Duration::from_millis(200))
+            .await
+            .unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&A8MiniError::Timeout("media response")));
+
+        mock.state().http_stalls = 1;
+        let mut downloaded = std::io::Cursor::new(Vec::new());
+        cam.download_media_with_retry(query.clone(), &mut downloaded, 2, Duration::from_millis(200))
+            .await?;
+        assert_eq!(downloaded.into_inner(), video);
+
+        // 0 attempts still downloads once
+        let mut downloaded = std::io::Cursor::new(Vec::new());
+        cam.download_media_with_retry(query, &mut downloaded, 0, Duration::from_millis(200))
+            .await?;
+        assert_eq!(downloaded.into_inner(), video);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_download_media_with_progress() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state()
+            .files
+            .insert("/photo/100SIYI_VID/REC_0001.mp4".to_string(), vec![7; 300_000]);
+        mock.state()
+            .files
+            .insert("/photo/100SIYI_VID/REC_0002.mp4".to_string(), Vec::new());
+        let cam = mock.connect().await?;
+
+        let mut progress: Vec<(u64, Option<u64>)> = Vec::new();
+        cam.download_media_with_progress(
+            control::A8MiniComplexHTTPQuery::GetMedia(control::MediaKind::Video, 1),
+            &mut Vec::new(),
+            |received, total| progress.push((received, total)),
+        )
+        .await?;
+        assert_eq!(progress.first(), Some(&(0, Some(300_000))));
+        assert_eq!(progress.last(), Some(&(300_000, Some(300_000))));
+        assert!(progress.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+
+        let mut empty_progress: Vec<(u64, Option<u64>)> = Vec::new();
+        cam.download_media_with_progress(
+            control::A8MiniComplexHTTPQuery::GetMedia(control::MediaKind::Video, 2),
+            &mut Vec::new(),
+            |received, total| empty_progress.push((received, total)),
+        )
+        .await?;
+        assert_eq!(empty_progress, vec![(0, Some(0))]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_typed_http_queries_round_trip() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().video_count = 2;
+        let cam = mock.connect().await?;
+        for _ in 0..3 {
+            cam.send_command_blind(control::A8MiniSimpleCommand::TakePicture).await?;
+        }
+        mock.wait_for(|state| state.photo_count == 3).await?;
+
+        let count = cam.send_http_query(control::MediaCountQuery(control::MediaKind::Photo)).await?;
+        assert_eq!(count.data.count, 3);
+        assert_eq!(count.data.path, "101SIYI_IMG");
+
+        let directories = cam.send_http_query(control::DirectoriesQuery(control::MediaKind::Video)).await?;
+        assert_eq!(
+            directories.data.directories,
+            vec![control::MediaDirectory { path: "100SIYI_VID".to_string(), count: Some(2) }]
+        );
+
+        let list = cam
+            .send_http_query(control::MediaListQuery { kind: control::MediaKind::Photo, start: 2, count: 5 })
+            .await?;
+        let names: Vec<&str> = list.data.list.iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(names, ["IMG_0003.jpg"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_mock_directories_round_trip() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().video_count = 2;
+        let cam = mock.connect().await?;
+
+        let directories = cam
+            .send_http_query(control::A8MiniComplexHTTPQuery::GetDirectories(control::MediaKind::Video))
+            .await?
+            .directories();
+        assert_eq!(
+            directories,
+            vec![control::MediaDirectory { path: "100SIYI_VID".to_string(), count: Some(2) }]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_mock_media_list_round_trip() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+        for _ in 0..3 {
+            cam.send_command_blind(control::A8MiniSimpleCommand::TakePicture).await?;
+        }
+        mock.wait_for(|state| state.photo_count == 3).await?;
+
+        let entries = cam
+            .send_http_query(control::A8MiniComplexHTTPQuery::GetMediaList {
+                kind: control::MediaKind::Photo,
+                start: 1,
+                count: 5,
+            })
+            .await?
+            .entries();
+        let names: Vec<&str> = entries.iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(names, ["IMG_0002.jpg", "IMG_0003.jpg"]);
+
+        // the listed URL is directly downloadable
+        let photo = reqwest::get(&entries[0].url).await?.bytes().await?;
+        assert_eq!(photo.to_vec(), test_support::fake_jpeg(2));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_http_query_retries_transient_failures() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().photo_count = 4;
+        let builder = mock.builder();
+        let cam = builder.clone().http_retry(3, Duration::from_millis(10)).build().await?;
+
+        mock.state().http_resets = 2;
+        assert_eq!(cam.get_photo_information().await?, 4);
+        assert_eq!(mock.state().http_requests, 3);
+
+        let cam = builder.http_retry(2, Duration::from_millis(10)).build().await?;
+        mock.state().http_resets = 2;
+        assert!(cam.get_photo_information().await.is_err());
+        Ok(())
+    }
+
+    #[derive(Debug)]
+    struct MissingPageQuery;
+
+    impl control::HTTPQuery for MissingPageQuery {
+        type Response = control::HTTPResponseData;
+
+        fn path(&self) -> String {
+            "/missing".to_string()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_http_query_does_not_retry_client_errors() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        let err = cam.send_http_query(MissingPageQuery).await.unwrap_err();
+        let status = err.downcast_ref::<reqwest::Error>().and_then(|e| e.status());
+        assert_eq!(status, Some(reqwest::StatusCode::NOT_FOUND));
+        assert_eq!(mock.state().http_requests, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_download_all_skips_existing_files() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        for _ in 0..3 {
+            mock.state().photo_count += 1;
+            let count = mock.state().photo_count;
+            mock.state()
+                .files
+                .insert(format!("/photo/101SIYI_IMG/IMG_{:0>4}.jpg", count), test_support::fake_jpeg(count));
+        }
+        let cam = mock.connect().await?;
+
+        let dest_dir = std::env::temp_dir().join(format!("a8mini-download-all-{}", std::process::id()));
+        tokio::fs::create_dir_all(&dest_dir).await?;
+        tokio::fs::write(dest_dir.join("IMG_0001.jpg"), test_support::fake_jpeg(1)).await?;
+        // wrong size, so downloaded again
+        tokio::fs::write(dest_dir.join("IMG_0002.jpg"), [0u8; 2]).await?;
+
+        let summary = cam.download_all(control::MediaKind::Photo, &dest_dir, 2).await?;
+        assert_eq!(summary.skipped, [dest_dir.join("IMG_0001.jpg")]);
+        assert_eq!(summary.downloaded.len(), 2);
+        assert!(summary.failed.is_empty());
+        for index in 1..=3 {
+            let path = dest_dir.join(format!("IMG_{:0>4}.jpg", index));
+            assert_eq!(tokio::fs::read(path).await?, test_support::fake_jpeg(index));
+        }
+
+        tokio::fs::remove_dir_all(&dest_dir).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_download_by_name() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state()
+            .files
+            .insert("/photo/101SIYI_IMG/IMG 0001 (copy).jpg".to_string(), vec![1, 2, 3]);
+        let cam = mock.connect().await?;
+
+        let photo = cam
+            .send_http_media_query(control::A8MiniComplexHTTPQuery::GetPhotoByName(
+                "IMG 0001 (copy).jpg".to_string(),
+            ))
+            .await?;
+        assert_eq!(photo, vec![1, 2, 3]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_stops_on_drop() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        let heartbeat = cam.spawn_heartbeat(Duration::from_millis(10));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        // commands still go through while the heartbeat runs
+        cam.get_attitude_information().await?;
+        drop(heartbeat);
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let sent = mock.state().heartbeats;
+        assert!(sent >= 3, "only {} heartbeats", sent);
+
+        // no more heartbeats arrive once it's dropped
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(mock.state().heartbeats, sent);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_send_command_gets_matching_ack() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        for _ in 0..20 {
+            let (attitude, firmware) = tokio::join!(
+                cam.send_command(control::A8MiniSimpleCommand::AttitudeInformation),
+                cam.send_command(control::A8MiniSimpleCommand::FirmwareVersionInformation),
+            );
+            assert_eq!(attitude?.command_id, 0x0D);
+            assert_eq!(firmware?.command_id, 0x01);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_command_skips_stale_ack() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        // rotation speed is ACKed but sent blind, leaving its ACK queued on the socket
+        cam.send_command_blind(control::A8MiniSimpleCommand::StopRotation).await?;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let frame = cam.send_command(control::A8MiniSimpleCommand::AttitudeInformation).await?;
+        assert_eq!(frame.command_id, 0x0D);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_command_retry_recovers_dropped_frame() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        mock.state().drop_next = 1;
+        let err = cam
+            .send_command(control::A8MiniSimpleCommand::FirmwareVersionInformation)
+            .await
+            .unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&A8MiniError::Timeout("command ACK")));
+
+        mock.state().drop_next = 2;
+        let frame = cam
+            .send_command_retry(control::A8MiniSimpleCommand::FirmwareVersionInformation, 3)
+            .await?;
+        assert_eq!(frame.command_id, 0x01);
+
+        mock.state().drop_next = 2;
+        let err = cam
+            .send_command_retry(control::A8MiniSimpleCommand::FirmwareVersionInformation, 2)
+            .await
+            .unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&A8MiniError::Timeout("command ACK")));
+
+        // 0 attempts still sends once
+        let frame = cam
+            .send_command_retry(control::A8MiniSimpleCommand::FirmwareVersionInformation, 0)
+            .await?;
+        assert_eq!(frame.command_id, 0x01);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_command_retry_skips_ack_of_earlier_attempt() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        // the first attempt's ACK arrives during the second attempt, ahead of the second's own
+        mock.state().reply_delays = [Duration::from_millis(700), Duration::from_millis(300)].into();
+        let frame = cam
+            .send_command_retry(control::A8MiniSimpleCommand::FirmwareVersionInformation, 2)
+            .await?;
+        assert_eq!(frame.sequence, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_run_sequence_sends_steps_in_order() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        let started = tokio::time::Instant::now();
+        cam.run_sequence(&[
+            (control::A8MiniSimpleCommand::Heartbeat.into(), Duration::from_millis(30)),
+            (control::A8MiniSimpleCommand::TakePicture.into(), Duration::from_millis(30)),
+            (control::A8MiniSimpleCommand::Heartbeat.into(), Duration::from_millis(30)),
+        ])
+        .await?;
+        assert!(started.elapsed() >= Duration::from_millis(90));
+
+        let state = mock.state();
+        assert_eq!(state.heartbeats, 2);
+        assert_eq!(state.photo_count, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_auto_center_and_wait() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().attitude.theta_yaw = 450;
+        mock.state().attitude.theta_pitch = -300;
+        let cam = mock.connect().await?;
+
+        let attitude = cam.auto_center_and_wait(Duration::from_secs(1)).await?;
+        assert_eq!((attitude.yaw_deg(), attitude.pitch_deg()), (0.0, 0.0));
+
+        mock.state().attitude.theta_yaw = 450;
+        mock.state().gimbal_stuck = true;
+        let err = cam.auto_center_and_wait(Duration::from_millis(200)).await.unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&A8MiniError::Timeout("gimbal to center")));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_point_at() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        let attitude = cam.point_at(30.0, -45.5, 0.5, Duration::from_secs(1)).await?;
+        assert_eq!((attitude.yaw_deg(), attitude.pitch_deg()), (30.0, -45.5));
+
+        mock.state().gimbal_stuck = true;
+        let err = cam.point_at(-30.0, 0.0, 0.5, Duration::from_millis(200)).await.unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&A8MiniError::Timeout("gimbal to reach target angle")));
+
+        assert!(cam.point_at(180.0, 0.0, 0.5, Duration::from_millis(200)).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_log_attitude_csv() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().attitude.theta_yaw = 455;
+        mock.state().attitude.v_roll = -3;
+        let cam = mock.connect().await?;
+
+        let mut log = Vec::new();
+        let rows = cam.log_attitude_csv(&mut log, 50, Duration::from_millis(200)).await?;
+        assert!(rows > 0);
+
+        let log = String::from_utf8(log)?;
+        let mut lines = log.lines();
+        assert_eq!(lines.next(), Some("Timestamp,Yaw,Pitch,Roll,V_Yaw,V_Pitch,V_Roll"));
+        let row: Vec<&str> = lines.next().unwrap().split(',').collect();
+        assert!(chrono::DateTime::parse_from_rfc3339(row[0]).is_ok());
+        assert_eq!(&row[1..], &["45.5", "0", "0", "0", "0", "-3"]);
+        assert_eq!(log.lines().count() as u64, rows + 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_log_attitude_ndjson() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().attitude.theta_yaw = 455;
+        mock.state().attitude.v_roll = -3;
+        let cam = mock.connect().await?;
+
+        let mut log = Vec::new();
+        let rows = cam.log_attitude_ndjson(&mut log, 50, Duration::from_millis(200)).await?;
+        assert!(rows > 0);
+
+        let log = String::from_utf8(log)?;
+        assert_eq!(log.lines().count() as u64, rows);
+        for line in log.lines() {
+            let value: serde_json::Value = serde_json::from_str(line)?;
+            assert!(chrono::DateTime::parse_from_rfc3339(value["timestamp"].as_str().unwrap()).is_ok());
+            assert_eq!(value["theta_yaw"], 455);
+            assert_eq!(value["v_roll"], -3);
+            let attitude: control::A8MiniAttitude = serde_json::from_value(value)?;
+            assert_eq!(attitude.theta_yaw, 455);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_log_attitude_csv_returns_write_errors() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        // writing to a duplex whose reader is gone fails with BrokenPipe
+        let (mut writer, reader) = tokio::io::duplex(64);
+        drop(reader);
+        assert!(cam.log_attitude_csv(&mut writer, 50, Duration::from_millis(100)).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_recv_buffer_size() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        // 8-byte header + 118-byte payload + CRC: a 128-byte frame
+        let long_payload: Vec<u8> = (0..118).collect();
+        mock.state().payload_overrides.insert(0x02, long_payload.clone());
+
+        // truncated to the default 64 bytes, the frame fails to parse
+        let cam = mock.connect().await?;
+        assert!(cam.send_command(control::A8MiniSimpleCommand::HardwareIDInformation).await.is_err());
+
+        let cam = mock.builder().recv_buffer_size(256).build().await?;
+        let frame = cam.send_command(control::A8MiniSimpleCommand::HardwareIDInformation).await?;
+        assert_eq!(frame.payload, long_payload);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_raw() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        let frame = cam.send_raw(0x01, &[]).await?;
+        assert_eq!(frame.command_id, 0x01);
+        assert_eq!(frame.payload.len(), 12);
+
+        mock.state().payload_overrides.insert(0x04, vec![0x00]);
+        let err = cam.send_raw(0x04, &[0x01]).await.unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&A8MiniError::CameraNack { command_id: 0x04, code: 0 }));
+
+        cam.send_raw_blind(0x0C, &[0x00]).await?;
+        mock.wait_for(|state| state.photo_count == 1).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_raw_blind_stamps_sequence() -> anyhow::Result<()> {
+        let camera = tokio::net::UdpSocket::bind("127.0.0.1:0").await?;
+        let cam = A8Mini::connect_to("127.0.0.1", &camera.local_addr()?.port().to_string(), "0", "0", "0").await?;
+
+        let mut sequences = Vec::new();
+        for _ in 0..2 {
+            cam.send_raw_blind(0x0C, &[0x00]).await?;
+            let mut buf = [0u8; 32];
+            let len = camera.recv(&mut buf).await?;
+            sequences.push(control::AckFrame::parse(&buf[..len])?.sequence);
+        }
+        assert_eq!(sequences, [0, 1]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_set_and_get_mode() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        for mode in [control::GimbalMode::Fpv, control::GimbalMode::Lock, control::GimbalMode::Follow] {
+            cam.set_mode(mode).await?;
+            assert_eq!(cam.get_mode().await?, mode);
+        }
+        assert_eq!(cam.get_gimbal_status().await?.mounting_direction, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_set_angle_with_speed_ramps_to_target() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        assert!(cam.set_angle_with_speed(10.0, 0.0, 0).await.is_err());
+        assert!(cam.set_angle_with_speed(10.0, 0.0, 101).await.is_err());
+        assert!(cam.set_angle_with_speed(200.0, 0.0, 50).await.is_err());
+
+        let started = tokio::time::Instant::now();
+        cam.set_angle_with_speed(20.0, -10.0, 100).await?;
+        assert!(started.elapsed() >= Duration::from_millis(200));
+        mock.wait_for(|state| state.angle_setpoints.last() == Some(&(200, -100))).await?;
+        let setpoints = mock.state().angle_setpoints.clone();
+        assert_eq!(setpoints.last(), Some(&(200, -100)));
+        // stepped there through intermediate setpoints, never turning back
+        assert!(setpoints.iter().any(|&(yaw, _)| yaw > 0 && yaw < 200), "setpoints {:?}", setpoints);
+        assert!(setpoints.windows(2).all(|pair| pair[0].0 <= pair[1].0 && pair[0].1 >= pair[1].1));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_raw_frame_stream_yields_pushed_frames() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+        let peer = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), cam.command_local_addr()?.port());
+
+        mock.push_frame(peer, &test_support::ack_frame(0x0D, 7, &[0; 12])).await?;
+        mock.push_frame(peer, b"garbage").await?;
+        mock.push_frame(peer, &test_support::ack_frame(0x0A, 8, &[1, 2])).await?;
+
+        let frames: Vec<_> = cam.raw_frame_stream().take(3).collect().await;
+        assert_eq!(frames.len(), 3);
+        let first = frames[0].as_ref().unwrap();
+        assert_eq!((first.command_id, first.sequence), (0x0D, 7));
+        assert!(frames[1].is_err());
+        let third = frames[2].as_ref().unwrap();
+        assert_eq!((third.command_id, third.payload.as_slice()), (0x0A, &[1u8, 2][..]));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_media_api_path() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().photo_count = 4;
+        let builder = mock.builder().http_retry(1, Duration::from_millis(10));
+
+        let default_path = builder.clone().build().await?;
+        assert_eq!(default_path.get_photo_information().await?, 4);
+
+        mock.state().media_api_path = "/media".to_string();
+        assert!(default_path.get_photo_information().await.is_err());
+        drop(default_path);
+        let custom_path = builder.media_api_path("/media").build().await?;
+        assert!(custom_path
+            .http_url(&control::A8MiniComplexHTTPQuery::GetMediaCount(control::MediaKind::Photo))
+            .contains("/media/api/v1/getmediacount"));
+        assert_eq!(custom_path.get_photo_information().await?, 4);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_media() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        let err = cam.get_latest_photo().await.unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&A8MiniError::NoMedia(control::MediaKind::Photo)));
+
+        for _ in 0..3 {
+            cam.send_command_blind(control::A8MiniSimpleCommand::TakePicture).await?;
+        }
+        mock.wait_for(|state| state.photo_count == 3).await?;
+        assert_eq!(cam.get_latest_photo().await?, test_support::fake_jpeg(3));
+
+        let err = cam.get_latest_video().await.unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&A8MiniError::NoMedia(control::MediaKind::Video)));
+        Ok(())
+    }
 
-        cam.send_command_blind(control::A8MiniSimpleCommand::RotateUp).await?;
-        sleep(Duration::from_millis(500));
+    #[tokio::test]
+    async fn test_get_latest_media_missing_file() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().video_count = 2;
+        let cam = mock.connect().await?;
+
+        let err = cam.get_latest_video().await.unwrap_err();
+        let status = err.downcast_ref::<reqwest::Error>().and_then(|e| e.status());
+        assert_eq!(status, Some(reqwest::StatusCode::NOT_FOUND));
+        Ok(())
+    }
 
-        cam.send_command_blind(control::A8MiniSimpleCommand::RotateDown).await?;
-        sleep(Duration::from_millis(500));
+    #[tokio::test]
+    async fn test_stream_attitude_data_stats() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+        mock.state().drop_next = 2;
+
+        let (mut rx, stats) = cam.stream_attitude_data_with_stats(100);
+        for _ in 0..3 {
+            rx.recv().await.expect("attitude stream ended");
+        }
+        // the task keeps polling, so later counts may already be in
+        assert!(stats.frames_received() >= 3);
+        assert_eq!(stats.timeouts(), 2);
+        assert!(stats.polls_sent() >= 5);
+        assert_eq!(stats.crc_failures(), 0);
+        Ok(())
+    }
 
+    #[tokio::test]
+    async fn test_require_limits() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let builder = mock.builder().require_limits(true);
+
+        let cam = builder.clone().build().await?;
+        assert_eq!(cam.model(), None);
+        cam.set_angle_deg(10.0, 0.0).await?;
+        assert_eq!(cam.model(), Some(control::CameraModel::A8Mini));
+        drop(cam);
+
+        mock.state().hardware_id = *b"FF0000000001";
+        let cam = builder.build().await?;
+        let err = cam.set_angle_deg(10.0, 0.0).await.unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&A8MiniError::ModelUnknown));
+        let err = cam.send_command_blind(control::A8MiniSimpleCommand::RotateLeft).await.unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&A8MiniError::ModelUnknown));
+        // stopping is always allowed
         cam.send_command_blind(control::A8MiniSimpleCommand::StopRotation).await?;
-        sleep(Duration::from_millis(1000));
 
-        cam.send_command_blind(control::A8MiniSimpleCommand::AutoCenter).await?;
+        // off by default
+        let cam = mock.connect().await?;
+        cam.send_command_blind(control::A8MiniSimpleCommand::RotateLeft).await?;
         Ok(())
     }
 
-    #[ignore]
     #[tokio::test]
-    async fn test_send_complex_commands_blind() -> anyhow::Result<()> {
-        let cam: A8Mini = A8Mini::connect().await?;
+    async fn test_point_presets() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+        cam.set_mode(control::GimbalMode::Fpv).await?;
+
+        cam.send_command(control::A8MiniSimpleCommand::PointDown).await?;
+        assert_eq!(cam.get_attitude_information().await?.pitch_deg(), -90.0);
+        cam.send_command(control::A8MiniSimpleCommand::PointForward).await?;
+        assert_eq!(cam.get_attitude_information().await?.pitch_deg(), 0.0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stream_attitude_data_timestamped() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().attitude.theta_pitch = -300;
+        let cam = mock.connect().await?;
+
+        let started = tokio::time::Instant::now();
+        let mut rx = cam.stream_attitude_data_timestamped(50);
+        let (first_at, first) = rx.recv().await.expect("attitude stream ended");
+        let (second_at, _) = rx.recv().await.expect("attitude stream ended");
+        assert_eq!(first.theta_pitch, -300);
+        assert!(first_at >= started);
+        // samples are at least the poll interval apart
+        assert!(second_at - first_at >= Duration::from_millis(20));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stream_attitude_data_timestamped_zero_hz() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().attitude.theta_pitch = -300;
+        let cam = mock.connect().await?;
+
+        // polls at 1 Hz instead of dividing by zero
+        let mut rx = cam.stream_attitude_data_timestamped(0);
+        let (_, attitude) = rx.recv().await.expect("attitude stream ended");
+        assert_eq!(attitude.theta_pitch, -300);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_normalize_mounting() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().attitude.theta_yaw = 300;
+        mock.state().attitude.theta_pitch = -450;
+        let builder = mock.builder().normalize_mounting(true);
+
+        let upright = builder.clone().build().await?;
+        assert_eq!(upright.mounting_direction().await?, control::MountingDirection::Normal);
+        let attitude = upright.get_attitude_information().await?;
+        assert_eq!((attitude.theta_yaw, attitude.theta_pitch), (300, -450));
+        drop(upright);
+
+        mock.state().mounting_direction = 2;
+        let inverted = builder.build().await?;
+        assert_eq!(inverted.mounting_direction().await?, control::MountingDirection::UpsideDown);
+        let attitude = inverted.get_attitude_information().await?;
+        assert_eq!((attitude.theta_yaw, attitude.theta_pitch), (-300, 450));
+        assert_eq!(inverted.poll_attitude().await?, attitude);
+
+        mock.state().mounting_direction = 0;
+        assert!(inverted.mounting_direction().await.is_err());
+
+        let mut stream = inverted.stream_attitude_data(20);
+        let streamed = tokio::time::timeout(Duration::from_secs(1), stream.recv()).await?;
+        assert_eq!(streamed, Some(attitude));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_set_angle_checks_model_limits() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        assert_eq!(cam.limits(), control::GimbalLimits::A8_MINI);
+        assert!(cam.set_angle_deg(200.0, 0.0).await.is_err());
+
+        mock.state().hardware_id = *b"7A0230000001";
+        cam.get_camera_info().await?;
+        assert_eq!(cam.limits(), control::GimbalLimits::ZT30);
+        assert!(cam.set_angle_deg(200.0, 0.0).await.is_err());
+
+        // an A2 mini can't turn at all
+        mock.state().hardware_id = *b"7502A2000001";
+        cam.get_camera_info().await?;
+        assert!(cam.set_angle_deg(10.0, 0.0).await.is_err());
+        cam.set_angle_deg(0.0, -45.0).await?;
+        mock.wait_for(|state| state.attitude.theta_pitch == -450).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_follow_target_skips_non_finite_bearings() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        // a lost target's NaN bearing is skipped, not fatal
+        cam.follow_target(stream::iter([(5.0, 0.0), (f32::NAN, 0.0), (0.0, f32::INFINITY)])).await?;
+        mock.wait_for(|state| (state.attitude.theta_yaw, state.attitude.theta_pitch) == (50, 0)).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_follow_target_coalesces_bearings() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        // a burst far faster than the command rate, ending out of range
+        let mut bearings: Vec<(f32, f32)> = (0..100).map(|i| (i as f32, -(i as f32) / 10.0)).collect();
+        bearings.push((200.0, -45.0));
+        let started = tokio::time::Instant::now();
+        cam.follow_target(stream::iter(bearings)).await?;
+        assert!(started.elapsed() < constants::FOLLOW_COMMAND_INTERVAL * 10);
+
+        mock.wait_for(|state| (state.attitude.theta_yaw, state.attitude.theta_pitch) == (1350, -450)).await?;
+
+        // paced bearings are each followed
+        let paced = stream::iter([(10.0, 0.0), (20.0, -10.0)]).then(|bearing| async move {
+            tokio::time::sleep(constants::FOLLOW_COMMAND_INTERVAL * 2).await;
+            bearing
+        });
+        cam.follow_target(paced).await?;
+        mock.wait_for(|state| (state.attitude.theta_yaw, state.attitude.theta_pitch) == (200, -100)).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_speed_guard_stops_rotation() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        let session = cam.speed_session();
+        session.set_speed(40, -20).await?;
+        mock.wait_for(|state| state.gimbal_speed == (40, -20)).await?;
+        session.stop().await?;
+        mock.wait_for(|state| state.gimbal_speed == (0, 0)).await?;
+
+        // dropped mid-motion, as on an early return
+        {
+            let session = cam.speed_session();
+            session.set_speed_normalized(1.0, 0.0, 0.1).await?;
+            mock.wait_for(|state| state.gimbal_speed == (100, 0)).await?;
+        }
+        mock.wait_for(|state| state.gimbal_speed == (0, 0)).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_coalesced_speed_never_drops_zero() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        cam.set_speed_with_coalescing(40, 10).await?;
+        mock.wait_for(|state| state.gimbal_speed == (40, 10)).await?;
+
+        for step in 1..=20 {
+            cam.set_speed_with_coalescing(step, -step).await?;
+            cam.set_speed_with_coalescing(0, 0).await?;
+        }
+        mock.wait_for(|state| state.gimbal_speed == (0, 0)).await?;
+        // no stale speed goes out once the coalescing interval has passed
+        tokio::time::sleep(constants::SPEED_COALESCE_INTERVAL * 2).await;
+        assert_eq!(mock.state().gimbal_speed, (0, 0));
+
+        // coalesced speeds wait for the rate limit, zero doesn't
+        let limited = A8MiniBuilder::from_config(cam.config())
+            .min_command_interval(Duration::from_millis(300))
+            .build()
+            .await?;
+        limited.send_command_blind(control::A8MiniComplexCommand::SetYawPitchSpeed(10, 10)).await?;
+        limited.set_speed_with_coalescing(30, 30).await?;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(mock.state().gimbal_speed, (10, 10));
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(mock.state().gimbal_speed, (30, 30));
+
+        let started = tokio::time::Instant::now();
+        limited.set_speed_with_coalescing(0, 0).await?;
+        assert!(started.elapsed() < Duration::from_millis(100));
+        mock.wait_for(|state| state.gimbal_speed == (0, 0)).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_recording_guard_stops_recording() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        let recording = cam.start_recording().await?;
+        mock.wait_for(|state| state.recording).await?;
+        recording.stop().await?;
+        mock.wait_for(|state| !state.recording).await?;
+
+        let recording = cam.start_recording().await?;
+        mock.wait_for(|state| state.recording).await?;
+        drop(recording);
+        mock.wait_for(|state| !state.recording).await?;
+        assert_eq!(mock.state().video_count, 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_attitude_while_recording() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().attitude.theta_yaw = 123;
+        let cam = mock.connect().await?;
+
+        let recording = cam.start_recording().await?;
+        let attitude = recording.as_ref().get_attitude_information().await?;
+        assert_eq!(attitude.theta_yaw, 123);
+        assert!(mock.state().recording);
+        recording.stop().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_health_reports_partial_failures() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().photo_count = 4;
+        mock.state().attitude.theta_roll = -5;
+        let cam = mock.builder().http_retry(1, Duration::ZERO).build().await?;
+
+        let health = cam.health().await?;
+        assert_eq!(health.attitude.map(|a| a.theta_roll), Some(-5));
+        assert_eq!(
+            health.storage,
+            Some(control::StorageInfo { sd_card_present: true, photo_count: Some(4), video_count: Some(0) })
+        );
+        assert!(health.gimbal_status.is_some() && health.firmware.is_some());
+
+        // HTTP API down: only the counts are missing
+        mock.state().http_resets = u32::MAX;
+        let health = cam.health().await?;
+        assert_eq!(
+            health.storage,
+            Some(control::StorageInfo { sd_card_present: true, photo_count: None, video_count: None })
+        );
+        assert!(health.attitude.is_some());
+
+        // nothing answers
+        mock.state().drop_next = u32::MAX;
+        assert!(matches!(cam.health().await, Err(A8MiniError::NoCameraFound(_))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_recording_state() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        let idle = control::RecordingState { active: false, elapsed_secs: 0 };
+        assert_eq!(cam.get_recording_state().await?, idle);
+
+        let recording = cam.start_recording().await?;
+        mock.wait_for(|state| state.recording).await?;
+        assert_eq!(cam.get_recording_state().await?, control::RecordingState { active: true, elapsed_secs: 0 });
+
+        *cam.recording_since.write().unwrap() = Some(tokio::time::Instant::now() - Duration::from_secs(5));
+        assert_eq!(cam.get_recording_state().await?.elapsed_secs, 5);
+
+        recording.stop().await?;
+        mock.wait_for(|state| !state.recording).await?;
+        assert_eq!(cam.get_recording_state().await?, idle);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_command_typed() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().attitude.theta_pitch = -200;
+        let cam = mock.connect().await?;
+
+        match cam.send_command_typed(control::A8MiniSimpleCommand::AttitudeInformation).await? {
+            control::AckPayload::Attitude(attitude) => assert_eq!(attitude.theta_pitch, -200),
+            other => panic!("expected attitude, got {:?}", other),
+        }
+        assert_eq!(
+            cam.send_command_typed(control::A8MiniSimpleCommand::HardwareIDInformation).await?,
+            control::AckPayload::HardwareId("7302A8000001".to_string())
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_discover_collects_responders() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let target = SocketAddr::new(mock.ip().parse()?, mock.command_port().parse()?);
+        assert_eq!(discover_at(target, Duration::from_millis(200)).await?, vec![target.ip()]);
+
+        // nothing listening
+        let silent = tokio::net::UdpSocket::bind("127.0.0.1:0").await?;
+        assert!(discover_at(silent.local_addr()?, Duration::from_millis(100)).await?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_attitude_request_frame() {
+        assert_eq!(
+            ATTITUDE_REQUEST.to_vec(),
+            control::Command::to_bytes(&control::A8MiniSimpleCommand::AttitudeInformation)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_poll_attitude() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().attitude.theta_pitch = -450;
+        let cam = mock.connect().await?;
+
+        assert_eq!(cam.poll_attitude().await?, cam.get_attitude_information().await?);
+        assert_eq!(cam.poll_attitude().await?.theta_pitch, -450);
+
+        mock.state().drop_next = 1;
+        assert_eq!(cam.poll_attitude().await, Err(A8MiniError::Timeout("attitude ACK")));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_poll_attitude_skips_late_ack() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        // the first poll's ACK arrives during the second poll, ahead of the second's own
+        mock.state().reply_delays = [Duration::from_millis(700), Duration::from_millis(300)].into();
+        assert_eq!(cam.poll_attitude().await, Err(A8MiniError::Timeout("attitude ACK")));
+        mock.state().attitude.theta_yaw = 100;
+        assert_eq!(cam.poll_attitude().await?.theta_yaw, 100);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ping_latency() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        mock.state().reply_delays.push_back(Duration::from_millis(50));
+        let latency = cam.ping_latency().await?;
+        assert!(latency >= Duration::from_millis(50) && latency < constants::RECV_TIMEOUT);
+
+        mock.state().drop_next = 1;
+        assert!(matches!(cam.ping_latency().await, Err(A8MiniError::Timeout(_))));
+
+        // waiting behind another exchange doesn't count as latency
+        mock.state().reply_delays.push_back(Duration::from_millis(300));
+        let (firmware, latency) = tokio::join!(cam.get_firmware_version(), async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            cam.ping_latency().await
+        });
+        firmware?;
+        assert!(latency? < Duration::from_millis(200));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_attitude_watch_shared_by_subscribers() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().attitude.theta_yaw = 100;
+        let cam = mock.connect().await?;
+
+        let mut display = cam.spawn_attitude_watch(50).await?;
+        let mut logger = display.clone();
+        assert_eq!(display.borrow().theta_yaw, 100);
+
+        mock.state().attitude.theta_yaw = 250;
+        display.wait_for(|attitude| attitude.theta_yaw == 250).await?;
+        logger.wait_for(|attitude| attitude.theta_yaw == 250).await?;
+
+        // a late subscriber starts from the latest value
+        assert_eq!(logger.clone().borrow().theta_yaw, 250);
+
+        // with the poller gone, the socket is free for ordinary requests again
+        drop((display, logger));
+        assert_eq!(cam.get_attitude_information().await?.theta_yaw, 250);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_zoom_to() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        assert_eq!(cam.get_max_zoom().await?, 6.0);
+        assert_eq!(cam.zoom_to(2.5).await?, 2.5);
+        assert_eq!(mock.state().zoom_tenths, 25);
+
+        let err = cam.zoom_to(6.5).await.unwrap_err();
+        assert!(matches!(err.downcast_ref(), Some(A8MiniError::OutOfRange { name: "zoom", .. })));
+        assert_eq!(cam.get_zoom().await?, 2.5);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_camera_info() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().codec_specs[2].width = 1280;
+        mock.state().codec_specs[2].height = 720;
+        let cam = mock.connect().await?;
+
+        let info = cam.get_camera_info().await?;
+        assert_eq!(info.model, control::CameraModel::A8Mini);
+        assert_eq!(info.hardware_id, "7302A8000001");
+        assert!(!info.hdr);
+        assert_eq!(info.main_stream_resolution, Some(control::Resolution::Uhd4k));
+        assert_eq!(info.sub_stream_resolution, Some(control::Resolution::Hd720));
+
+        mock.state().hardware_id = *b"9901A8000001";
+        assert_eq!(cam.get_camera_info().await?.model, control::CameraModel::Unknown("99".to_string()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_model_specific_commands_gated() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().hardware_id = *b"7A01ZT000001";
+        let cam = mock.connect().await?;
+
+        // nothing is gated before the model is known
+        cam.send_command_blind(control::A8MiniSimpleCommand::SetVideoOutputHDMI).await?;
+
+        cam.get_camera_info().await?;
+        assert_eq!(cam.model(), Some(control::CameraModel::Zt30));
+        let err = cam
+            .send_command_blind(control::A8MiniSimpleCommand::SetVideoOutputHDMI)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<A8MiniError>(),
+            Some(&A8MiniError::UnsupportedByModel {
+                command: "SetVideoOutputHDMI".to_string(),
+                model: control::CameraModel::Zt30,
+            })
+        );
+
+        cam.set_model(None);
+        cam.send_command_blind(control::A8MiniSimpleCommand::SetVideoOutputHDMI).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_single_axis_angles_hold_other_axis() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().attitude.theta_yaw = 300;
+        mock.state().attitude.theta_pitch = -150;
+        let cam = mock.connect().await?;
+
+        cam.set_pitch_deg(-45.0).await?;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let attitude = mock.state().attitude;
+        assert_eq!((attitude.theta_yaw, attitude.theta_pitch), (300, -450));
+
+        cam.set_yaw_deg(-90.0).await?;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let attitude = mock.state().attitude;
+        assert_eq!((attitude.theta_yaw, attitude.theta_pitch), (-900, -450));
+
+        mock.state().reply_delays = [Duration::from_millis(300)].into();
+        let err = cam.set_pitch_deg(0.0).await.unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&A8MiniError::Timeout("fresh attitude")));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_codec_specs_round_trip() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        cam.set_codec_specs(control::CodecConfig::hd_720p(2000).stream_type(1).codec(control::VideoCodec::H264))
+            .await?;
+
+        let specs = cam.get_codec_specs(1).await?;
+        assert_eq!(
+            (specs.stream_type, specs.codec, specs.width, specs.height, specs.bitrate_kbps),
+            (1, 1, 1280, 720, 2000)
+        );
+        // other streams are untouched
+        assert_eq!(cam.get_codec_specs(0).await?, mock.state().codec_specs[0]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reset_codec_defaults() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+        cam.set_codec_specs(control::CodecConfig::hd_720p(1000).codec(control::VideoCodec::H264)).await?;
+        cam.set_codec_specs(control::CodecConfig::uhd_4k(20000).stream_type(2)).await?;
+
+        cam.reset_codec_defaults().await?;
+        for config in control::CodecConfig::factory_defaults() {
+            let specs = cam.get_codec_specs(config.stream_type).await?;
+            assert_eq!(
+                (specs.codec, specs.width, specs.height, specs.bitrate_kbps),
+                (config.codec as u8, config.width, config.height, config.bitrate_kbps)
+            );
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_set_and_get_resolution() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().codec_specs[0].codec = 1;
+        mock.state().codec_specs[0].bitrate_kbps = 8000;
+        let cam = mock.connect().await?;
+
+        for resolution in [control::Resolution::Hd1080, control::Resolution::Uhd4k] {
+            cam.set_resolution(resolution).await?;
+            assert_eq!(cam.get_resolution().await?, resolution);
+        }
+        let specs = mock.state().codec_specs[0];
+        assert_eq!((specs.codec, specs.bitrate_kbps), (1, 8000));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_set_codec_specs_rejects_before_sending() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        let err = cam
+            .set_codec_specs(control::CodecConfig::uhd_4k(50000))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<A8MiniError>(),
+            Some(A8MiniError::UnsupportedCodecConfig { bitrate_kbps: 50000, .. })
+        ));
+        assert_eq!(mock.state().codec_specs[0].bitrate_kbps, 15000);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_camera_nack() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().payload_overrides.insert(0x21, vec![0x00, 0x00]);
+        let cam = mock.connect().await?;
+
+        let err = cam.set_codec_specs(control::CodecConfig::uhd_4k(8000)).await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<A8MiniError>(),
+            Some(&A8MiniError::CameraNack { command_id: 0x21, code: 0 })
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_take_photo_and_download() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().photo_count = 6;
+        let cam = mock.connect().await?;
+
+        let photo = cam.take_photo_and_download().await?;
+        assert_eq!(photo, test_support::fake_jpeg(7));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_take_photo_and_download_missing_file() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().lose_photos = true;
+        let cam = mock.connect().await?;
+
+        let err = cam.take_photo_and_download().await.unwrap_err();
+        let status = err.downcast_ref::<reqwest::Error>().and_then(|e| e.status());
+        assert_eq!(status, Some(reqwest::StatusCode::NOT_FOUND));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_photo_count_increase() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().photo_count = 2;
+        let cam = mock.connect().await?;
+
+        let err = cam
+            .wait_for_photo_count_increase(2, Duration::from_millis(250))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<A8MiniError>(),
+            Some(&A8MiniError::Timeout("photo count to increase"))
+        );
+
+        mock.state().photo_count = 3;
+        assert_eq!(cam.wait_for_photo_count_increase(2, Duration::ZERO).await?, 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sync_time_now() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        let before = std::time::SystemTime::now();
+        cam.sync_time_now().await?;
+        let utc = mock.state().utc_time.expect("time was not set");
+        // sent with microsecond resolution
+        assert!(utc + Duration::from_micros(1) > before && utc <= std::time::SystemTime::now());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_socket_addrs() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.connect().await?;
+
+        assert_ne!(cam.command_local_addr()?.port(), 0);
+        assert_ne!(cam.http_local_addr()?.port(), 0);
+        assert_eq!(cam.command_peer_addr()?.port().to_string(), mock.command_port());
+        assert_eq!(cam.http_peer_addr()?.port().to_string(), mock.http_port());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fully_custom_ports() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        // reserve two free local ports
+        let free_port = || -> anyhow::Result<String> {
+            Ok(std::net::UdpSocket::bind("127.0.0.1:0")?.local_addr()?.port().to_string())
+        };
+        let (command_port, http_port) = (free_port()?, free_port()?);
+        let cam = mock.builder()
+            .local_command_port(command_port.as_str())
+            .local_http_port(http_port.as_str())
+            .rtsp_port("554")
+            .build()
+            .await?;
+
+        assert_eq!(cam.command_local_addr()?.port().to_string(), command_port);
+        assert_eq!(cam.http_local_addr()?.port().to_string(), http_port);
+        assert_eq!(cam.rtsp_url(), "rtsp://127.0.0.1:554/main.264");
+        cam.get_firmware_version().await?;
+        assert_eq!(cam.get_photo_information().await?, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_local_bind_ip_is_honored() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        // another loopback address than the mock's, so the source address shows the bind
+        let loopback = std::net::IpAddr::V4(std::net::Ipv4Addr::new(127, 0, 0, 2));
+        let cam = mock.builder().local_bind_ip(loopback).build().await?;
+
+        assert_eq!(cam.command_local_addr()?.ip(), loopback);
+        assert_eq!(cam.http_local_addr()?.ip(), loopback);
+        cam.get_photo_information().await?;
+        assert_eq!(mock.state().last_http_peer.map(|peer| peer.ip()), Some(loopback));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_verify_on_connect() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let builder = mock.builder().verify_on_connect(true);
+        builder.clone().build().await?;
+
+        // bound, but never answers
+        let silent = tokio::net::UdpSocket::bind("127.0.0.1:0").await?;
+        let silent_port = silent.local_addr()?.port().to_string();
+        let err = builder.camera_command_port(silent_port.as_str()).build().await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref(),
+            Some(&A8MiniError::NoCameraFound(format!("127.0.0.1:{}", silent_port)))
+        );
+        Ok(())
+    }
 
-        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchSpeed(50, 50)).await?;
-        sleep(Duration::from_millis(1000));
+    #[tokio::test]
+    async fn test_config_round_trip() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = mock.builder().recv_timeout(Duration::from_millis(100)).build().await?;
+
+        let config = cam.config().clone();
+        assert_eq!(config.camera_ip, mock.ip());
+        assert_eq!(config.command_port, mock.command_port());
+        assert_eq!(config.recv_timeout, Duration::from_millis(100));
+
+        let json = serde_json::to_string(&config)?;
+        let restored: ConnectionConfig = serde_json::from_str(&json)?;
+        let again = A8MiniBuilder::from_config(&restored).build().await?;
+        assert_eq!(again.config(), &config);
+        again.get_attitude_information().await?;
+
+        // the configured timeout applies to ACKs
+        mock.state().drop_next = 1;
+        let started = tokio::time::Instant::now();
+        assert!(again.get_attitude_information().await.is_err());
+        assert!(started.elapsed() < constants::RECV_TIMEOUT);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_min_command_interval() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let unlimited = mock.connect().await?;
+        let limited = A8MiniBuilder::from_config(unlimited.config())
+            .min_command_interval(Duration::from_millis(50))
+            .build()
+            .await?;
+
+        let started = tokio::time::Instant::now();
+        for _ in 0..5 {
+            unlimited.send_command_blind(control::A8MiniSimpleCommand::RotateUp).await?;
+        }
+        assert!(started.elapsed() < Duration::from_millis(50));
 
-        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchSpeed(50, 10)).await?;
-        sleep(Duration::from_millis(1000));
+        // the first send goes straight out, the other four wait their turn
+        let started = tokio::time::Instant::now();
+        for _ in 0..5 {
+            limited.send_command_blind(control::A8MiniSimpleCommand::RotateUp).await?;
+        }
+        assert!(started.elapsed() >= Duration::from_millis(200));
+
+        // idle time counts towards the interval
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let started = tokio::time::Instant::now();
+        limited.send_command_blind(control::A8MiniSimpleCommand::StopRotation).await?;
+        assert!(started.elapsed() < Duration::from_millis(50));
+
+        // ACKed commands don't wait behind a blind send held back by the limiter
+        let limited = A8MiniBuilder::from_config(unlimited.config())
+            .min_command_interval(Duration::from_millis(300))
+            .build()
+            .await?;
+        limited.send_command_blind(control::A8MiniSimpleCommand::RotateUp).await?;
+        let (blind, acked) = tokio::join!(
+            limited.send_command_blind(control::A8MiniSimpleCommand::StopRotation),
+            async {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                let started = tokio::time::Instant::now();
+                limited.get_firmware_version().await?;
+                anyhow::Ok(started.elapsed())
+            }
+        );
+        blind?;
+        assert!(acked? < Duration::from_millis(200));
+        Ok(())
+    }
 
-        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchSpeed(-25, -15)).await?;
-        sleep(Duration::from_millis(6000));
+    #[tokio::test]
+    async fn test_min_command_interval_paces_raw_blind_sends() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        let cam = A8MiniBuilder::from_config(mock.connect().await?.config())
+            .min_command_interval(Duration::from_millis(50))
+            .build()
+            .await?;
 
-        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchSpeed(0, 0)).await?;
-        sleep(Duration::from_millis(1000));
+        // raw and typed blind sends share one limiter
+        let started = tokio::time::Instant::now();
+        for _ in 0..2 {
+            cam.send_raw_blind(0x0C, &[0x00]).await?;
+            cam.send_command_blind(control::A8MiniSimpleCommand::TakePicture).await?;
+        }
+        assert!(started.elapsed() >= Duration::from_millis(150));
+        Ok(())
+    }
 
-        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(90, 0)).await?;
-        sleep(Duration::from_millis(1000));
+    #[tokio::test]
+    async fn test_crc_init() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+        mock.state().crc_init = 0xffff;
+        mock.state().attitude.theta_yaw = 300;
+
+        // the camera ignores frames sealed with the documented init
+        let cam = mock.connect().await?;
+        assert!(cam.get_attitude_information().await.is_err());
+
+        let cam = A8MiniBuilder::from_config(cam.config()).crc_init(0xffff).build().await?;
+        assert_eq!(cam.get_attitude_information().await?.theta_yaw, 300);
+        assert_eq!(cam.poll_attitude().await?.theta_yaw, 300);
+        cam.set_angle_deg(10.0, 0.0).await?;
+        mock.wait_for(|state| state.attitude.theta_yaw == 100).await?;
+        Ok(())
+    }
 
-        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(90, -90)).await?;
-        sleep(Duration::from_millis(1000));
+    #[tokio::test]
+    async fn test_connect_timeout() -> anyhow::Result<()> {
+        // bound, but never answers the handshake
+        let silent = tokio::net::UdpSocket::bind("127.0.0.1:0").await?;
+        let started = tokio::time::Instant::now();
+        let err = A8Mini::builder()
+            .camera_ip("127.0.0.1")
+            .camera_command_port(silent.local_addr()?.port().to_string())
+            .local_command_port("0")
+            .local_http_port("0")
+            .verify_on_connect(true)
+            .connect_timeout(Duration::from_millis(100))
+            .build()
+            .await
+            .unwrap_err();
+        assert_eq!(err.downcast_ref(), Some(&A8MiniError::Timeout("connection setup")));
+        assert!(started.elapsed() < constants::RECV_TIMEOUT);
+        Ok(())
+    }
 
-        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(-90, -90)).await?;
-        sleep(Duration::from_millis(1000));
+    #[tokio::test]
+    async fn test_rtsp_urls() -> anyhow::Result<()> {
+        let mock = test_support::MockCamera::spawn().await?;
+
+        let cam = mock.connect().await?;
+        assert_eq!(cam.rtsp_url(), "rtsp://127.0.0.1:8554/main.264");
+        assert_eq!(cam.rtsp_sub_url(), "rtsp://127.0.0.1:8554/sub.264");
+
+        let cam = mock.builder()
+            .rtsp_port("554")
+            .rtsp_main_path("/video1")
+            .rtsp_sub_path("/video2")
+            .build()
+            .await?;
+        assert_eq!(cam.rtsp_url(), "rtsp://127.0.0.1:554/video1");
+        assert_eq!(cam.rtsp_sub_url(), "rtsp://127.0.0.1:554/video2");
+        Ok(())
+    }
 
-        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(-90, 0)).await?;
-        sleep(Duration::from_millis(1000));
+    #[ignore]
+    #[tokio::test]
+    async fn test_take_and_download_photo() -> anyhow::Result<()> {
+        let cam: A8Mini = A8Mini::connect().await?;
 
-        cam.send_command_blind(control::A8MiniComplexCommand::SetYawPitchAngle(0, 0)).await?;
-        sleep(Duration::from_millis(1000));
+        let baseline = cam.get_photo_information().await?;
+        cam.send_command_blind(control::A8MiniSimpleCommand::TakePicture)
+            .await?;
+        let num_pictures = cam
+            .wait_for_photo_count_increase(baseline, constants::CAPTURE_TIMEOUT)
+            .await?;
+        let picture_bytes = cam
+            .send_http_media_query(control::A8MiniComplexHTTPQuery::GetMedia(control::MediaKind::Photo, num_pictures as u32))
+            .await?;
+        File::create("tmp.jpeg")
+            .await?
+            .write_all(&picture_bytes)
+            .await?;
 
-        cam.send_command_blind(control::A8MiniSimpleCommand::AutoCenter).await?;
         Ok(())
     }
 
+    #[ignore]
+    #[tokio::test]
+    async fn test_send_simple_commands_blind() -> anyhow::Result<()> {
+        use control::A8MiniSimpleCommand::*;
+
+        let cam: A8Mini = A8Mini::connect().await?;
+        let ms = Duration::from_millis;
+
+        cam.run_sequence(&[
+            (RotateLeft.into(), ms(500)),
+            (RotateRight.into(), ms(1000)),
+            (RotateLeft.into(), ms(500)),
+            (StopRotation.into(), ms(0)),
+            (RotateUp.into(), ms(500)),
+            (RotateDown.into(), ms(500)),
+            (StopRotation.into(), ms(1000)),
+            (AutoCenter.into(), ms(0)),
+        ])
+        .await
+    }
+
+    #[ignore]
+    #[tokio::test]
+    async fn test_send_complex_commands_blind() -> anyhow::Result<()> {
+        use control::A8MiniComplexCommand::*;
+
+        let cam: A8Mini = A8Mini::connect().await?;
+        let ms = Duration::from_millis;
+
+        cam.run_sequence(&[
+            (SetYawPitchSpeed(50, 50).into(), ms(1000)),
+            (SetYawPitchSpeed(50, 10).into(), ms(1000)),
+            (SetYawPitchSpeed(-25, -15).into(), ms(6000)),
+            (SetYawPitchSpeed(0, 0).into(), ms(1000)),
+            (SetYawPitchAngle(90, 0).into(), ms(1000)),
+            (SetYawPitchAngle(90, -90).into(), ms(1000)),
+            (SetYawPitchAngle(-90, -90).into(), ms(1000)),
+            (SetYawPitchAngle(-90, 0).into(), ms(1000)),
+            (SetYawPitchAngle(0, 0).into(), ms(1000)),
+            (control::A8MiniSimpleCommand::AutoCenter.into(), ms(0)),
+        ])
+        .await
+    }
+
     #[ignore]
     #[tokio::test]
     async fn test_send_command_with_ack() -> anyhow::Result<()> {
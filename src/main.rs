@@ -1,78 +1,44 @@
 use std::io::{self, Write};
 
 use a8mini_camera_rs::control::{
-    A8MiniComplexCommand, A8MiniComplexHTTPQuery, A8MiniSimpleCommand, A8MiniSimpleHTTPQuery,
-    A8MiniAttitude,
+    A8MiniComplexCommand, A8MiniComplexHTTPQuery, A8MiniSimpleCommand,
+    A8MiniAttitude, AckFrame, CodecConfig, MediaKind, VideoCodec,
 };
-use a8mini_camera_rs::A8Mini;
+use a8mini_camera_rs::{A8Mini, A8MiniError};
 use chrono::Utc;
 use tokio::fs::{File, OpenOptions};
 use tokio::io::AsyncWriteExt;
 use tracing::Level;
-use bincode::deserialize;
 
 fn print_ascii_command_table() {
-    let simple_commands = [
-        "AutoCenter",
-        "RotateUp",
-        "RotateDown",
-        "RotateRight",
-        "RotateLeft",
-        "StopRotation",
-        "ZoomIn",
-        "ZoomOut",
-        "ZoomMax",
-        "MaxZoomInformation",
-        "FocusIn",
-        "FocusOut",
-        "TakePicture",
-        "RecordVideo",
-        "Rotate100100",
-        "CameraInformation",
-        "AutoFocus",
-        "HardwareIDInformation",
-        "FirmwareVersionInformation",
-        "SetLockMode",
-        "SetFollowMode",
-        "SetFPVMode",
-        "AttitudeInformation",
-        "SetVideoOutputHDMI",
-        "SetVideoOutputCVBS",
-        "SetVideoOutputOff",
-        "LaserRangefinderInformation",
-        "RebootCamera",
-        "RebootGimbal",
-        "Resolution4k",
-        "Heartbeat",
-        "GimbalStatus",
-    ];
+    let simple_commands: Vec<&str> = A8MiniSimpleCommand::all().iter().map(|command| command.name()).collect();
 
     let complex_commands = [
         "SetYawPitchSpeed(i8, i8)",
         "SetYawPitchAngle(i16, i16)",
-        "SetTimeUTC(u64)",
+        "SetTimeUTC(secs[.micros])",
         "GetCodecSpecs(u8)",
         "SetCodecSpecs(u8, u8, u16, u16, u16, u8)",
-        "LogAttitudeStream", 
-    ];
-
-    let simple_queries = [
-        "GetDirectoriesPhotos",
-        "GetDirectoriesVideos",
-        "GetMediaCountPhotos",
-        "GetMediaCountVideos",
+        "RequestGimbalDataStream(u8, u8)",
+        "SetAbsoluteZoom(u8, u8)",
+        "GetZoomLevel",
+        "LogAttitudeStream",
     ];
 
-    let complex_queries = [
-        "GetPhoto(u32)",
-        "GetVideo(u32)"
+    // kind: 0 photo, 1 video
+    let queries = [
+        "GetDirectories(kind)",
+        "GetMediaCount(kind)",
+        "GetMedia(kind, u32)",
+        "GetMediaList(kind, u32, u32)",
+        "GetPhotoByName(String)",
+        "GetVideoByName(String)",
     ];
 
     let all_printed = [
-        simple_commands.to_vec(),
+        simple_commands,
         complex_commands.to_vec(),
-        simple_queries.to_vec(),
-        complex_queries.to_vec(),
+        queries.to_vec(),
     ];
 
     for list in all_printed.iter() {
@@ -98,12 +64,11 @@ async fn main() -> anyhow::Result<()> {
     print_ascii_command_table();
 
     loop {
-        let full_command: &str;
         println!("Awaiting command: ");
         let stdin = io::stdin();
         let buf = &mut String::new();
         stdin.read_line(buf)?;
-        full_command = buf.strip_suffix("\n").unwrap();
+        let full_command: &str = buf.strip_suffix("\n").unwrap();
 
         let destructured_command: Vec<&str> = full_command.split(" ").collect();
         let command: &str = destructured_command[0];
@@ -122,7 +87,7 @@ async fn main() -> anyhow::Result<()> {
                 .open(&filename)
                 .await?;
 
-            file.write_all(b"Timestamp,Yaw,Pitch,Roll,V_Yaw,V_Pitch,V_Roll\n").await?;
+            file.write_all(A8MiniAttitude::CSV_HEADER.as_bytes()).await?;
             println!("Logging to: {}", filename);
             println!("Polling Attitude (0x0D) at ~100Hz... (Press Ctrl+C to stop)");
 
@@ -142,34 +107,26 @@ async fn main() -> anyhow::Result<()> {
                 let recv_content = tokio::time::timeout(
                     std::time::Duration::from_millis(50),
                     camera.command_socket.recv_from(&mut buffer),
-                ).await.or_else(|e| {
+                ).await.inspect_err(|_| {
                     // Propagate error through, but also print out vitals:
                     eprintln!("Did not get a response before 50ms timeout. Is the camera connected?");
-                    Err(e)
                 })?;
                 
                 match recv_content {
                     Ok((len, _)) => {
                         // Check if it is the correct packet (Attitude ID: 0x0D / 13)
-                        if len >= 20 && buffer[7] == 0x0D {
-                            let data_slice = &buffer[8..20];
-                            
-                            if let Ok(attitude) = deserialize::<A8MiniAttitude>(data_slice) {
-                                let yaw = attitude.theta_yaw as f32 / 10.0;
-                                let pitch = attitude.theta_pitch as f32 / 10.0;
-                                let roll = attitude.theta_roll as f32 / 10.0;
+                        let frame = AckFrame::parse(&buffer[..len]).ok().filter(|frame| frame.command_id == 0x0D);
+                        if let Some(frame) = frame {
+                            if let Ok(attitude) = A8MiniAttitude::from_payload(&frame.payload) {
+                                let yaw = attitude.yaw_deg();
+                                let pitch = attitude.pitch_deg();
+                                let roll = attitude.roll_deg();
                                 
                                 // 1. ALWAYS Log to file (Every single packet)
-                                let log_line = format!(
-                                    "{},{},{},{},{},{},{}\n",
-                                    Utc::now().to_rfc3339(),
-                                    yaw, pitch, roll,
-                                    attitude.v_yaw, attitude.v_pitch, attitude.v_roll
-                                );
-                                file.write_all(log_line.as_bytes()).await?;
+                                file.write_all(attitude.to_csv_row(Utc::now()).as_bytes()).await?;
 
                                 // 2. ONLY Print to console every 10th packet (~10Hz update rate)
-                                if print_counter % 10 == 0 {
+                                if print_counter.is_multiple_of(10) {
                                     print!("\rAttitude: Y: {:>6.1} | P: {:>6.1} | R: {:>6.1}", yaw, pitch, roll);
                                     io::stdout().flush().unwrap();
                                 }
@@ -198,44 +155,10 @@ async fn main() -> anyhow::Result<()> {
         }
         // end of logging block
 
-        let simple_command_enum: Option<A8MiniSimpleCommand> = match command {
-            "AutoCenter" => Some(A8MiniSimpleCommand::AutoCenter),
-            "RotateUp" => Some(A8MiniSimpleCommand::RotateUp),
-            "RotateDown" => Some(A8MiniSimpleCommand::RotateDown),
-            "RotateRight" => Some(A8MiniSimpleCommand::RotateRight),
-            "RotateLeft" => Some(A8MiniSimpleCommand::RotateLeft),
-            "StopRotation" => Some(A8MiniSimpleCommand::StopRotation),
-            "ZoomIn" => Some(A8MiniSimpleCommand::ZoomIn),
-            "ZoomOut" => Some(A8MiniSimpleCommand::ZoomOut),
-            "ZoomMax" => Some(A8MiniSimpleCommand::ZoomMax),
-            "MaxZoomInformation" => Some(A8MiniSimpleCommand::MaxZoomInformation),
-            "FocusIn" => Some(A8MiniSimpleCommand::FocusIn),
-            "FocusOut" => Some(A8MiniSimpleCommand::FocusOut),
-            "TakePicture" => Some(A8MiniSimpleCommand::TakePicture),
-            "RecordVideo" => Some(A8MiniSimpleCommand::RecordVideo),
-            "Rotate100100" => Some(A8MiniSimpleCommand::Rotate100100),
-            "CameraInformation" => Some(A8MiniSimpleCommand::CameraInformation),
-            "AutoFocus" => Some(A8MiniSimpleCommand::AutoFocus),
-            "HardwareIDInformation" => Some(A8MiniSimpleCommand::HardwareIDInformation),
-            "FirmwareVersionInformation" => Some(A8MiniSimpleCommand::FirmwareVersionInformation),
-            "SetLockMode" => Some(A8MiniSimpleCommand::SetLockMode),
-            "SetFollowMode" => Some(A8MiniSimpleCommand::SetFollowMode),
-            "SetFPVMode" => Some(A8MiniSimpleCommand::SetFPVMode),
-            "AttitudeInformation" => Some(A8MiniSimpleCommand::AttitudeInformation),
-            "SetVideoOutputHDMI" => Some(A8MiniSimpleCommand::SetVideoOutputHDMI),
-            "SetVideoOutputCVBS" => Some(A8MiniSimpleCommand::SetVideoOutputCVBS),
-            "SetVideoOutputOff" => Some(A8MiniSimpleCommand::SetVideoOutputOff),
-            "LaserRangefinderInformation" => Some(A8MiniSimpleCommand::LaserRangefinderInformation),
-            "RebootCamera" => Some(A8MiniSimpleCommand::RebootCamera),
-            "RebootGimbal" => Some(A8MiniSimpleCommand::RebootGimbal),
-            "Resolution4k" => Some(A8MiniSimpleCommand::Resolution4k),
-            "Heartbeat" => Some(A8MiniSimpleCommand::Heartbeat),
-            "GimbalStatus" => Some(A8MiniSimpleCommand::GimbalStatus),
-            _ => None,
-        };
+        let simple_command_enum: Option<A8MiniSimpleCommand> = command.parse().ok();
 
         if let Some(cmd) = simple_command_enum {
-            println!("Sending Simple Command {:?}", cmd);
+            println!("Sending Simple Command {}", cmd);
             let camera: A8Mini = A8Mini::connect().await?;
 
             match cmd {
@@ -246,6 +169,13 @@ async fn main() -> anyhow::Result<()> {
                     }
                 },
 
+                A8MiniSimpleCommand::MaxZoomInformation => {
+                    match camera.get_max_zoom().await {
+                        Ok(zoom) => println!("Max zoom: {:.1}x", zoom),
+                        Err(e) => println!("Failed to get max zoom: {:?}", e),
+                    }
+                },
+
                 A8MiniSimpleCommand::FirmwareVersionInformation => {
                     match camera.get_firmware_version().await {
                         Ok(info) => println!("{}", info),
@@ -265,86 +195,75 @@ async fn main() -> anyhow::Result<()> {
             continue;
         }
 
-        let complex_command_enum: Option<A8MiniComplexCommand> = match command {
-            "SetYawPitchSpeed" => {
-                let yaw: i8 = destructured_command[1].parse().unwrap_or(0);
-                let pitch: i8 = destructured_command[2].parse().unwrap_or(0);
-                Some(A8MiniComplexCommand::SetYawPitchSpeed(yaw, pitch))
+        let complex_command_enum: Option<A8MiniComplexCommand> = match full_command.parse() {
+            Ok(command) => Some(command),
+            // no time given: sync to now
+            Err(_) if full_command == "SetTimeUTC" => {
+                Some(A8MiniComplexCommand::SetTimeUTC(std::time::SystemTime::now()))
             }
-            "SetYawPitchAngle" => {
-                let yaw: i16 = destructured_command[1].parse().unwrap_or(0);
-                let pitch: i16 = destructured_command[2].parse().unwrap_or(0);
-                Some(A8MiniComplexCommand::SetYawPitchAngle(yaw, pitch))
+            Err(A8MiniError::UnknownCommand(_)) => None,
+            Err(e) => {
+                println!("{}", e);
+                continue;
             }
-            "SetTimeUTC" => {
-                let epoch: u64 = destructured_command[1].parse().unwrap_or(0);
-                Some(A8MiniComplexCommand::SetTimeUTC(epoch))
+        };
+
+        if let Some(complex_command) = complex_command_enum {
+            println!("Sending Complex Command {}", complex_command);
+            let camera: A8Mini = A8Mini::connect().await?;
+
+            if let A8MiniComplexCommand::GetCodecSpecs(stream_type) = complex_command {
+                match camera.get_codec_specs(stream_type).await {
+                    Ok(specs) => println!("{:?}", specs),
+                    Err(e) => println!("Failed to get codec specs: {:?}", e),
+                }
+                continue;
             }
-            "GetCodecSpecs" => {
-                let stream_type: u8 = destructured_command[1].parse().unwrap_or(0);
-                Some(A8MiniComplexCommand::GetCodecSpecs(stream_type))
+
+            if let A8MiniComplexCommand::GetZoomLevel = complex_command {
+                match camera.get_zoom().await {
+                    Ok(zoom) => println!("Zoom: {:.1}x", zoom),
+                    Err(e) => println!("Failed to get zoom level: {:?}", e),
+                }
+                continue;
             }
-            "SetCodecSpecs" => {
-                let stream_type: u8 = destructured_command[1].parse().unwrap_or(0);
-                Some(A8MiniComplexCommand::SetCodecSpecs(
-                    stream_type,
-                    2,
-                    3840,
-                    2160,
-                    50000,
-                    0,
-                ))
+
+            // go through the validated path rather than sending arbitrary encoder settings
+            if let A8MiniComplexCommand::SetCodecSpecs(stream_type, codec, width, height, bitrate_kbps, _) =
+                complex_command
+            {
+                let Some(codec) = VideoCodec::from_byte(codec) else {
+                    println!("Unknown codec {}, expected 1 (H.264) or 2 (H.265)", codec);
+                    continue;
+                };
+                let config = CodecConfig { stream_type, codec, width, height, bitrate_kbps };
+                if let Err(e) = camera.set_codec_specs(config).await {
+                    println!("Failed to set codec specs: {}", e);
+                }
+                continue;
             }
-            _ => None,
-        };
 
-        if complex_command_enum.is_some() {
-            println!(
-                "Sending Complex Command {:?}",
-                complex_command_enum.unwrap()
-            );
-            let camera: A8Mini = A8Mini::connect().await?;
             camera
-                .send_command_blind(complex_command_enum.unwrap())
+                .send_command_blind(complex_command)
                 .await?;
             continue;
         }
 
-        let simple_query_enum: Option<A8MiniSimpleHTTPQuery> = match command {
-            "GetDirectoriesPhotos" => Some(A8MiniSimpleHTTPQuery::GetDirectoriesPhotos),
-            "GetDirectoriesVideos" => Some(A8MiniSimpleHTTPQuery::GetDirectoriesVideos),
-            "GetMediaCountPhotos" => Some(A8MiniSimpleHTTPQuery::GetMediaCountPhotos),
-            "GetMediaCountVideos" => Some(A8MiniSimpleHTTPQuery::GetMediaCountVideos),
-            _ => None,
-        };
-
-        if simple_query_enum.is_some() {
-            println!("Sending Simple HTTP Query {:?}", simple_query_enum.unwrap());
-            let camera: A8Mini = A8Mini::connect().await?;
-            let response = camera.send_http_query(simple_query_enum.unwrap()).await?;
-            println!("{:?}", response);
-            continue;
-        }
-
-        let complex_query_enum: Option<A8MiniComplexHTTPQuery> = match command {
-            "GetPhoto" => {
-                let photo_ind: u32 = destructured_command[1].parse().unwrap_or(0);
-                Some(A8MiniComplexHTTPQuery::GetPhoto(photo_ind as u32))
-            }
-            "GetVideo" => {
-                let video_ind: u32 = destructured_command[1].parse().unwrap_or(0);
-                Some(A8MiniComplexHTTPQuery::GetVideo(video_ind as u32))
+        let complex_query_enum: Option<A8MiniComplexHTTPQuery> = match full_command.parse() {
+            Ok(query) => Some(query),
+            Err(A8MiniError::UnknownCommand(_)) => None,
+            Err(e) => {
+                println!("{}", e);
+                continue;
             }
-            _ => None,
         };
 
-        if complex_query_enum.is_some() {
-            let complex_query = complex_query_enum.unwrap();
-            println!("Sending Complex HTTP Query {:?}", complex_query);
+        if let Some(complex_query) = complex_query_enum {
+            println!("Sending Complex HTTP Query {}", complex_query);
             let camera: A8Mini = A8Mini::connect().await?;
 
             match complex_query {
-                A8MiniComplexHTTPQuery::GetPhoto(_) => {
+                A8MiniComplexHTTPQuery::GetMedia(MediaKind::Photo, _) | A8MiniComplexHTTPQuery::GetPhotoByName(_) => {
                     let image_bytes = camera.send_http_media_query(complex_query).await?;
 
                     let dir = "./tmp";
@@ -356,21 +275,24 @@ async fn main() -> anyhow::Result<()> {
                         .write_all(&image_bytes)
                         .await?;
                 }
-                A8MiniComplexHTTPQuery::GetVideo(_) => {
-                    let video_bytes = camera.send_http_media_query(complex_query).await?;
-
+                A8MiniComplexHTTPQuery::GetMedia(MediaKind::Video, _) | A8MiniComplexHTTPQuery::GetVideoByName(_) => {
                     let dir = "./tmp";
                     let timestamp = Utc::now().timestamp_millis();
                     let vid_path = format!("{}/VID-{}.mp4", dir, timestamp);
 
-                    File::create(&vid_path)
-                        .await?
-                        .write_all(&video_bytes)
-                        .await?;
-                } // _ => {
-                  //   let response = camera.send_http_query(complex_query).await?;
-                  //   println!("{:?}", response);
-                  // }
+                    let mut vid_file = File::create(&vid_path).await?;
+                    camera.download_media_to(complex_query, &mut vid_file).await?;
+                }
+                A8MiniComplexHTTPQuery::GetDirectories(_) | A8MiniComplexHTTPQuery::GetMediaCount(_) => {
+                    let response = camera.send_http_query(complex_query).await?;
+                    println!("{:?}", response);
+                }
+                _ => {
+                    let response = camera.send_http_query(complex_query).await?;
+                    for entry in response.entries() {
+                        println!("{}\t{}", entry.name, entry.url);
+                    }
+                }
             };
 
             continue;
@@ -0,0 +1,65 @@
+//! Conversions from `A8MiniAttitude` into nalgebra rotations, for pose estimators built on it.
+//!
+//! Angles compose in the gimbal's order: yaw about z, then pitch about y, then roll about x,
+//! the same as `A8MiniAttitude::to_quaternion`.
+
+use crate::control::A8MiniAttitude;
+use nalgebra::{Rotation3, UnitQuaternion};
+
+impl A8MiniAttitude {
+    /// Roll, pitch and yaw in radians, the argument order of nalgebra's `from_euler_angles`.
+    pub fn euler_angles(&self) -> (f32, f32, f32) {
+        (
+            self.roll_deg().to_radians(),
+            self.pitch_deg().to_radians(),
+            self.yaw_deg().to_radians(),
+        )
+    }
+}
+
+impl From<&A8MiniAttitude> for UnitQuaternion<f32> {
+    fn from(attitude: &A8MiniAttitude) -> Self {
+        let (roll, pitch, yaw) = attitude.euler_angles();
+        UnitQuaternion::from_euler_angles(roll, pitch, yaw)
+    }
+}
+
+impl From<&A8MiniAttitude> for Rotation3<f32> {
+    fn from(attitude: &A8MiniAttitude) -> Self {
+        let (roll, pitch, yaw) = attitude.euler_angles();
+        Rotation3::from_euler_angles(roll, pitch, yaw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::Vector3;
+
+    fn attitude(theta_yaw: i16, theta_pitch: i16, theta_roll: i16) -> A8MiniAttitude {
+        A8MiniAttitude { theta_yaw, theta_pitch, theta_roll, v_yaw: 0, v_pitch: 0, v_roll: 0 }
+    }
+
+    #[test]
+    fn test_quaternion_matches_known_orientation() {
+        // yaw 90° then pitch 90° about y: the optical (x) axis ends up along -z
+        let q = UnitQuaternion::from(&attitude(900, 900, 0));
+        let expected = UnitQuaternion::from_axis_angle(&Vector3::z_axis(), std::f32::consts::FRAC_PI_2)
+            * UnitQuaternion::from_axis_angle(&Vector3::y_axis(), std::f32::consts::FRAC_PI_2);
+        assert!(q.angle_to(&expected) < 1e-5);
+        assert!((q * Vector3::x() - Vector3::new(0.0, 0.0, -1.0)).norm() < 1e-5);
+
+        let [w, x, y, z] = attitude(900, 900, 0).to_quaternion();
+        assert!((q.into_inner().coords - Vector3::new(x, y, z).push(w)).norm() < 1e-5);
+    }
+
+    #[test]
+    fn test_rotation_round_trips_euler_angles() {
+        let attitude = attitude(-450, 200, 150);
+        let (roll, pitch, yaw) = Rotation3::from(&attitude).euler_angles();
+        let expected = attitude.euler_angles();
+        assert!((roll - expected.0).abs() < 1e-5);
+        assert!((pitch - expected.1).abs() < 1e-5);
+        assert!((yaw - expected.2).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,61 @@
+//! Snapshots from the live RTSP stream, without the camera saving a photo to its SD card.
+//!
+//! Decoding is done by `ffmpeg`, which must be installed and on `PATH`. It is run once per
+//! snapshot, so each capture pays for connecting to the stream and waiting for a keyframe.
+
+use crate::{constants, A8Mini, A8MiniError};
+use std::process::Stdio;
+use tokio::process::Command;
+use tracing::debug;
+
+/// Bytes of `ffmpeg`'s stderr kept in `A8MiniError::FrameCapture`.
+const STDERR_TAIL_LEN: usize = 500;
+
+impl A8Mini {
+    /// Grabs the first decodable frame of the main stream and returns it as a JPEG. Fails with
+    /// `A8MiniError::Timeout` if no frame arrives within `constants::RTSP_CAPTURE_TIMEOUT`.
+    pub async fn capture_rtsp_frame(&self) -> Result<Vec<u8>, A8MiniError> {
+        capture_frame("ffmpeg", &self.rtsp_main_url).await
+    }
+}
+
+/// Runs `program` as ffmpeg against `url`, collecting the JPEG it writes to stdout.
+async fn capture_frame(program: &str, url: &str) -> Result<Vec<u8>, A8MiniError> {
+    debug!("Capturing a frame from {} with {}.", url, program);
+    let child = Command::new(program)
+        .args(["-loglevel", "error", "-rtsp_transport", "tcp", "-i", url])
+        .args(["-frames:v", "1", "-f", "image2", "-c:v", "mjpeg", "pipe:1"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    let output = tokio::time::timeout(constants::RTSP_CAPTURE_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| A8MiniError::Timeout("RTSP frame"))??;
+    if !output.status.success() || output.stdout.is_empty() {
+        let tail = &output.stderr[output.stderr.len().saturating_sub(STDERR_TAIL_LEN)..];
+        let tail = String::from_utf8_lossy(tail);
+        return Err(A8MiniError::FrameCapture(format!("{}: {}", output.status, tail.trim())));
+    }
+    Ok(output.stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_missing_ffmpeg_is_io_error() {
+        let err = capture_frame("a8mini-no-such-ffmpeg", "rtsp://127.0.0.1:1/main.264").await.unwrap_err();
+        assert!(matches!(err, A8MiniError::Io { kind: std::io::ErrorKind::NotFound, .. }));
+    }
+
+    #[tokio::test]
+    async fn test_failed_capture_keeps_stderr() {
+        // `false` exits non-zero without output, like ffmpeg failing to reach the stream
+        let err = capture_frame("false", "rtsp://127.0.0.1:1/main.264").await.unwrap_err();
+        assert!(matches!(err, A8MiniError::FrameCapture(_)));
+    }
+}
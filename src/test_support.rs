@@ -0,0 +1,596 @@
+//! Loopback stand-in for the camera so round-trips can be exercised without hardware.
+//!
+//! Enabled for the crate's own tests and, for downstream crates, with the `mock` feature.
+
+use std::collections::{HashMap, VecDeque};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream, UdpSocket};
+use tokio::task::JoinHandle;
+
+use crate::{checksum, constants, control, A8Mini, A8MiniBuilder};
+
+/// State served by a `MockCamera`. Tests may change it at any time through `MockCamera::state`.
+#[derive(Debug, Clone)]
+pub struct MockState {
+    pub attitude: control::A8MiniAttitude,
+    /// Camera, gimbal and zoom firmware words as sent in the firmware ACK.
+    pub firmware: [u32; 3],
+    /// Returned by `HardwareIDInformation`; the first two characters name the model.
+    pub hardware_id: [u8; 12],
+    /// Current and maximum zoom factor, in tenths.
+    pub zoom_tenths: u16,
+    pub max_zoom_tenths: u16,
+    pub photo_count: i32,
+    pub video_count: i32,
+    /// Yaw and pitch speed from the last `0x07` rotation command.
+    pub gimbal_speed: (i8, i8),
+    /// Toggled by `RecordVideo`.
+    pub recording: bool,
+    /// Encoding parameters of the recording, main and sub streams.
+    pub codec_specs: [control::CodecSpecs; 3],
+    /// Last time set with `SetTimeUTC`.
+    pub utc_time: Option<SystemTime>,
+    /// Number of `Heartbeat` frames received.
+    pub heartbeats: u32,
+    /// Media files served over HTTP, keyed by request path (e.g. `/photo/101SIYI_IMG/IMG_0001.jpg`).
+    pub files: HashMap<String, Vec<u8>>,
+    /// Count pictures without storing them in `files`, as when the SD card write fails.
+    pub lose_photos: bool,
+    /// Every absolute angle commanded, oldest first, in tenths of a degree.
+    pub angle_setpoints: Vec<(i16, i16)>,
+    /// 1: normal, 2: upside down, as reported by `GimbalStatus`.
+    pub mounting_direction: u8,
+    /// 0: lock, 1: follow, 2: FPV, as reported by `GimbalStatus`.
+    pub motion_mode: u8,
+    /// Ignore motion commands, as if the gimbal were blocked.
+    pub gimbal_stuck: bool,
+    /// Answer `Range` requests with `206 Partial Content`. When off, ranges are ignored.
+    pub honor_range: bool,
+    /// Stall the next media response after this many body bytes, simulating a hung transfer.
+    pub stall_after: Option<usize>,
+    /// Number of upcoming command frames to ignore, simulating packet loss.
+    pub drop_next: u32,
+    /// HTTP requests received so far.
+    pub http_requests: u32,
+    /// Address the last HTTP connection came from.
+    pub last_http_peer: Option<SocketAddr>,
+    /// Close this many upcoming HTTP connections without answering, as during boot.
+    pub http_resets: u32,
+    /// Keep this many upcoming HTTP connections open without answering, as a hung web server.
+    pub http_stalls: u32,
+    /// ACK payloads sent instead of the canned ones, keyed by command ID.
+    pub payload_overrides: HashMap<u8, Vec<u8>>,
+    /// CRC16 initial value requests are checked and ACKs are sealed with.
+    pub crc_init: u16,
+    /// Prefix the media API is served under.
+    pub media_api_path: String,
+    /// Delays applied to the next ACKs, one per ACK, simulating a slow link.
+    pub reply_delays: VecDeque<Duration>,
+}
+
+impl Default for MockState {
+    fn default() -> Self {
+        MockState {
+            attitude: control::A8MiniAttitude {
+                theta_yaw: 0,
+                theta_pitch: 0,
+                theta_roll: 0,
+                v_yaw: 0,
+                v_pitch: 0,
+                v_roll: 0,
+            },
+            firmware: [0x6e_03_02_01, 0x73_00_04_04, 0x00_00_00_00],
+            hardware_id: *b"7302A8000001",
+            zoom_tenths: 10,
+            max_zoom_tenths: 60,
+            photo_count: 0,
+            video_count: 0,
+            gimbal_speed: (0, 0),
+            recording: false,
+            codec_specs: [0, 1, 2].map(|stream_type| control::CodecSpecs {
+                stream_type,
+                codec: 2,
+                width: 3840,
+                height: 2160,
+                bitrate_kbps: 15000,
+                framerate: 30,
+            }),
+            utc_time: None,
+            heartbeats: 0,
+            files: HashMap::new(),
+            lose_photos: false,
+            angle_setpoints: Vec::new(),
+            mounting_direction: 1,
+            motion_mode: 1,
+            gimbal_stuck: false,
+            honor_range: true,
+            stall_after: None,
+            drop_next: 0,
+            http_requests: 0,
+            last_http_peer: None,
+            http_resets: 0,
+            http_stalls: 0,
+            payload_overrides: HashMap::new(),
+            crc_init: constants::CRC16_INIT,
+            media_api_path: constants::MEDIA_API_PATH.to_string(),
+            reply_delays: VecDeque::new(),
+        }
+    }
+}
+
+/// Fake A8 mini listening on loopback: a UDP command socket answering with canned ACKs and a
+/// minimal HTTP server for the media API. Background tasks are stopped on drop.
+#[derive(Debug)]
+pub struct MockCamera {
+    command_addr: SocketAddr,
+    http_addr: SocketAddr,
+    command_socket: Arc<UdpSocket>,
+    state: Arc<Mutex<MockState>>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl MockCamera {
+    /// Binds ephemeral loopback ports and starts serving the default `MockState`.
+    pub async fn spawn() -> anyhow::Result<Self> {
+        let command_socket = Arc::new(UdpSocket::bind("127.0.0.1:0").await?);
+        let http_listener = TcpListener::bind("127.0.0.1:0").await?;
+        let state = Arc::new(Mutex::new(MockState::default()));
+
+        let mut camera = MockCamera {
+            command_addr: command_socket.local_addr()?,
+            http_addr: http_listener.local_addr()?,
+            command_socket: command_socket.clone(),
+            state: state.clone(),
+            tasks: Vec::new(),
+        };
+
+        camera.tasks.push(tokio::spawn(serve_commands(command_socket, state.clone())));
+        camera.tasks.push(tokio::spawn(serve_http(http_listener, state)));
+
+        Ok(camera)
+    }
+
+    /// Connects an `A8Mini` to this mock using ephemeral local ports.
+    pub async fn connect(&self) -> anyhow::Result<A8Mini> {
+        A8Mini::connect_to(
+            &self.ip(),
+            &self.command_port(),
+            &self.http_port(),
+            "0",
+            "0",
+        )
+        .await
+    }
+
+    /// An `A8MiniBuilder` pointed at this mock using ephemeral local ports, for tests that need
+    /// more than `connect`'s defaults.
+    pub fn builder(&self) -> A8MiniBuilder {
+        A8Mini::builder()
+            .camera_ip(self.ip())
+            .camera_command_port(self.command_port())
+            .camera_http_port(self.http_port())
+            .local_command_port("0")
+            .local_http_port("0")
+    }
+
+    pub fn ip(&self) -> String {
+        self.command_addr.ip().to_string()
+    }
+
+    pub fn command_port(&self) -> String {
+        self.command_addr.port().to_string()
+    }
+
+    pub fn http_port(&self) -> String {
+        self.http_addr.port().to_string()
+    }
+
+    /// Sends `frame` unsolicited from the command socket to `peer`, like firmware pushing data.
+    pub async fn push_frame(&self, peer: SocketAddr, frame: &[u8]) -> anyhow::Result<()> {
+        self.command_socket.send_to(frame, peer).await?;
+        Ok(())
+    }
+
+    /// Locks the served state for inspection or modification.
+    pub fn state(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap()
+    }
+
+    /// Polls the served state until `done` holds, failing after a second. For checking the
+    /// effect of blind sends, which return before the mock has handled them.
+    pub async fn wait_for(&self, done: impl Fn(&MockState) -> bool) -> anyhow::Result<()> {
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(1);
+        loop {
+            let state = self.state().clone();
+            if done(&state) {
+                return Ok(());
+            }
+            if tokio::time::Instant::now() >= deadline {
+                anyhow::bail!("mock state never matched: {:?}", state);
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    }
+}
+
+impl Drop for MockCamera {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+/// Builds a camera-originated frame (CTRL = ack_pack) with a valid CRC16.
+pub fn ack_frame(command_id: u8, sequence: u16, payload: &[u8]) -> Vec<u8> {
+    let mut byte_arr: Vec<u8> = vec![0x55, 0x66, 0x02];
+    byte_arr.extend_from_slice(&(payload.len() as u16).to_le_bytes());
+    byte_arr.extend_from_slice(&sequence.to_le_bytes());
+    byte_arr.push(command_id);
+    byte_arr.extend_from_slice(payload);
+    byte_arr.extend_from_slice(&checksum::crc16_calc(&byte_arr, constants::CRC16_INIT));
+    byte_arr
+}
+
+/// Minimal JPEG-looking bytes (SOI, a marker identifying the photo, EOI).
+pub fn fake_jpeg(index: i32) -> Vec<u8> {
+    let mut bytes = vec![0xFF, 0xD8];
+    bytes.extend_from_slice(&index.to_le_bytes());
+    bytes.extend_from_slice(&[0xFF, 0xD9]);
+    bytes
+}
+
+/// Canned reply for a request frame, or `None` for commands the camera doesn't ACK.
+fn respond(state: &mut MockState, request: &control::AckFrame) -> Option<Vec<u8>> {
+    let payload: Vec<u8> = match request.command_id {
+        // heartbeat
+        0x00 => {
+            state.heartbeats += 1;
+            return None;
+        }
+        // firmware version
+        0x01 => state.firmware.iter().flat_map(|word| word.to_le_bytes()).collect(),
+        // hardware ID
+        0x02 => state.hardware_id.to_vec(),
+        // centering snaps straight to home (1: one-key center, 3: center) or down (4)
+        0x08 => {
+            let pitch = match request.payload.first() {
+                Some(0x01 | 0x03) => Some(0),
+                Some(0x04) => Some(-900),
+                _ => None,
+            };
+            if let (Some(pitch), false) = (pitch, state.gimbal_stuck) {
+                state.attitude.theta_yaw = 0;
+                state.attitude.theta_pitch = pitch;
+            }
+            vec![0x01]
+        }
+        // absolute angle, reached instantly; ACKs with the current attitude
+        0x0E => {
+            let p = request.payload.get(..4)?;
+            let setpoint = (i16::from_le_bytes([p[0], p[1]]), i16::from_le_bytes([p[2], p[3]]));
+            state.angle_setpoints.push(setpoint);
+            if !state.gimbal_stuck {
+                (state.attitude.theta_yaw, state.attitude.theta_pitch) = setpoint;
+            }
+            let attitude = &state.attitude;
+            [attitude.theta_yaw, attitude.theta_pitch, attitude.theta_roll]
+                .iter()
+                .flat_map(|value| value.to_le_bytes())
+                .collect()
+        }
+        // absolute zoom, applied instantly
+        0x0F => {
+            let p = request.payload.get(..2)?;
+            state.zoom_tenths = (p[0] as u16 * 10 + p[1] as u16).min(state.max_zoom_tenths);
+            vec![0x01]
+        }
+        0x16 => zoom_payload(state.max_zoom_tenths),
+        0x18 => zoom_payload(state.zoom_tenths),
+        // rotation speed, then like zoom and focus a success status byte
+        0x07 => {
+            let p = request.payload.get(..2)?;
+            state.gimbal_speed = (p[0] as i8, p[1] as i8);
+            vec![0x01]
+        }
+        // zoom and focus answer with a success status byte
+        0x04..=0x06 => vec![0x01],
+        // gimbal status: HDR off, HDMI out
+        0x0A => vec![0x00, 0x00, 0x00, state.recording as u8, state.motion_mode, state.mounting_direction, 0x00, 0x00],
+        // take picture / record / modes
+        0x0C => {
+            match request.payload.first() {
+                Some(0x00) => {
+                    state.photo_count += 1;
+                    if !state.lose_photos {
+                        state.files.insert(
+                            format!("/photo/101SIYI_IMG/IMG_{:0>4}.jpg", state.photo_count),
+                            fake_jpeg(state.photo_count),
+                        );
+                    }
+                }
+                // recording toggles; stopping completes a file
+                Some(0x02) => {
+                    state.recording = !state.recording;
+                    if !state.recording {
+                        state.video_count += 1;
+                    }
+                }
+                Some(mode @ 0x03..=0x05) => state.motion_mode = mode - 3,
+                _ => {}
+            }
+            return None;
+        }
+        // attitude
+        0x0D => {
+            let attitude = &state.attitude;
+            [
+                attitude.theta_yaw,
+                attitude.theta_pitch,
+                attitude.theta_roll,
+                attitude.v_yaw,
+                attitude.v_pitch,
+                attitude.v_roll,
+            ]
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect()
+        }
+        // get codec specs
+        0x20 => {
+            let specs = state.codec_specs.get(*request.payload.first()? as usize)?;
+            let mut payload = vec![specs.stream_type, specs.codec];
+            payload.extend_from_slice(&specs.width.to_le_bytes());
+            payload.extend_from_slice(&specs.height.to_le_bytes());
+            payload.extend_from_slice(&specs.bitrate_kbps.to_le_bytes());
+            payload.push(specs.framerate);
+            payload
+        }
+        // set codec specs, echoed back on the next get
+        0x21 => {
+            let p = &request.payload;
+            if p.len() < 9 {
+                return None;
+            }
+            let specs = state.codec_specs.get_mut(p[0] as usize)?;
+            specs.codec = p[1];
+            specs.width = u16::from_le_bytes([p[2], p[3]]);
+            specs.height = u16::from_le_bytes([p[4], p[5]]);
+            specs.bitrate_kbps = u16::from_le_bytes([p[6], p[7]]);
+            vec![p[0], 0x01]
+        }
+        // set UTC time
+        0x30 => {
+            let micros: [u8; 8] = request.payload.get(..8)?.try_into().ok()?;
+            state.utc_time = Some(UNIX_EPOCH + Duration::from_micros(u64::from_le_bytes(micros)));
+            vec![0x01]
+        }
+        _ => return None,
+    };
+
+    let payload = state.payload_overrides.get(&request.command_id).cloned().unwrap_or(payload);
+    let mut reply = ack_frame(request.command_id, request.sequence, &payload);
+    control::stamp_crc(&mut reply, state.crc_init);
+    Some(reply)
+}
+
+/// Integer and tenths bytes of a zoom ACK.
+fn zoom_payload(tenths: u16) -> Vec<u8> {
+    vec![(tenths / 10) as u8, (tenths % 10) as u8]
+}
+
+async fn serve_commands(socket: Arc<UdpSocket>, state: Arc<Mutex<MockState>>) {
+    let mut recv_buffer = [0u8; 256];
+
+    loop {
+        let Ok((recv_len, peer)) = socket.recv_from(&mut recv_buffer).await else {
+            continue;
+        };
+        let crc_init = state.lock().unwrap().crc_init;
+        let Ok(request) = control::AckFrame::parse_with_crc_init(&recv_buffer[..recv_len], crc_init) else {
+            continue;
+        };
+
+        let (reply, delay) = {
+            let mut state = state.lock().unwrap();
+            if state.drop_next > 0 {
+                state.drop_next -= 1;
+                continue;
+            }
+            let reply = respond(&mut state, &request);
+            let delay = reply.as_ref().and_then(|_| state.reply_delays.pop_front());
+            (reply, delay)
+        };
+        match (reply, delay) {
+            (Some(reply), Some(delay)) => {
+                let socket = socket.clone();
+                tokio::spawn(async move {
+                    tokio::time::sleep(delay).await;
+                    let _ = socket.send_to(&reply, peer).await;
+                });
+            }
+            (Some(reply), None) => {
+                let _ = socket.send_to(&reply, peer).await;
+            }
+            (None, _) => {}
+        }
+    }
+}
+
+async fn serve_http(listener: TcpListener, state: Arc<Mutex<MockState>>) {
+    loop {
+        let Ok((stream, peer)) = listener.accept().await else {
+            continue;
+        };
+        state.lock().unwrap().last_http_peer = Some(peer);
+        tokio::spawn(handle_http(stream, state.clone()));
+    }
+}
+
+async fn handle_http(mut stream: TcpStream, state: Arc<Mutex<MockState>>) {
+    let mut request = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+        match stream.read(&mut chunk).await {
+            Ok(0) | Err(_) => return,
+            Ok(len) => request.extend_from_slice(&chunk[..len]),
+        }
+    }
+
+    let stall = {
+        let mut state = state.lock().unwrap();
+        state.http_requests += 1;
+        if state.http_resets > 0 {
+            state.http_resets -= 1;
+            return;
+        }
+        let stall = state.http_stalls > 0;
+        state.http_stalls = state.http_stalls.saturating_sub(1);
+        stall
+    };
+    if stall {
+        std::future::pending::<()>().await;
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let path = request.split_whitespace().nth(1).unwrap_or("/").to_string();
+    let host = request
+        .lines()
+        .find_map(|line| line.strip_prefix("host: ").or_else(|| line.strip_prefix("Host: ")))
+        .unwrap_or("127.0.0.1")
+        .to_string();
+    let range_start: Option<usize> = request.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        let start = value.trim().strip_prefix("bytes=")?.strip_suffix('-')?;
+        name.eq_ignore_ascii_case("range").then(|| start.parse().ok())?
+    });
+
+    let (status, content_type, body, stall_after) = {
+        let mut state = state.lock().unwrap();
+        let (status, content_type, body) = route(&state, &host, &path);
+        let is_media = content_type != "application/json" && status.starts_with("200");
+        let stall_after = if is_media { state.stall_after.take() } else { None };
+        let range_start = range_start.filter(|_| is_media && state.honor_range);
+        match range_start {
+            Some(start) if start <= body.len() => ("206 Partial Content", content_type, body[start..].to_vec(), stall_after),
+            _ => (status, content_type, body, stall_after),
+        }
+    };
+
+    let header = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    );
+    let _ = stream.write_all(header.as_bytes()).await;
+    if let Some(sent) = stall_after {
+        let _ = stream.write_all(&body[..sent.min(body.len())]).await;
+        let _ = stream.flush().await;
+        // keep the connection open without sending the rest
+        std::future::pending::<()>().await;
+    }
+    let _ = stream.write_all(&body).await;
+    let _ = stream.shutdown().await;
+}
+
+/// Returns status line, content type and body for a request path.
+fn route(state: &MockState, host: &str, path: &str) -> (&'static str, &'static str, Vec<u8>) {
+    let (endpoint, query) = path.split_once('?').unwrap_or((path, ""));
+
+    match endpoint.strip_prefix(state.media_api_path.as_str()) {
+        Some("/api/v1/getmediacount") => {
+            let (media_type, media_path, count) = if query.contains("media_type=1") {
+                (1, "100SIYI_VID", state.video_count)
+            } else {
+                (0, "101SIYI_IMG", state.photo_count)
+            };
+            let body = format!(
+                r#"{{"code":200,"data":{{"media_type":{},"path":"{}","count":{}}},"success":true,"message":""}}"#,
+                media_type, media_path, count
+            );
+            ("200 OK", "application/json", body.into_bytes())
+        }
+        Some("/api/v1/getdirectories") => {
+            let (media_type, media_path, count) = if query.contains("media_type=1") {
+                (1, "100SIYI_VID", state.video_count)
+            } else {
+                (0, "101SIYI_IMG", state.photo_count)
+            };
+            let body = format!(
+                r#"{{"code":200,"data":{{"media_type":{},"directories":[{{"path":"{}","count":{}}}]}},"success":true,"message":""}}"#,
+                media_type, media_path, count
+            );
+            ("200 OK", "application/json", body.into_bytes())
+        }
+        Some("/api/v1/getmedialist") => {
+            let (media_type, media_path) = if query.contains("media_type=1") {
+                (1, "100SIYI_VID")
+            } else {
+                (0, "101SIYI_IMG")
+            };
+            let start: usize = query_param(query, "start").and_then(|v| v.parse().ok()).unwrap_or(0);
+            let count: usize = query_param(query, "count").and_then(|v| v.parse().ok()).unwrap_or(usize::MAX);
+
+            let prefix = format!("/photo/{}/", media_path);
+            let mut paths: Vec<&String> = state.files.keys().filter(|p| p.starts_with(&prefix)).collect();
+            paths.sort();
+            let list: Vec<String> = paths
+                .into_iter()
+                .skip(start)
+                .take(count)
+                .map(|p| {
+                    format!(
+                        r#"{{"name":"{}","url":"http://{}{}","size":{}}}"#,
+                        &p[prefix.len()..],
+                        host,
+                        p,
+                        state.files[p].len()
+                    )
+                })
+                .collect();
+
+            let body = format!(
+                r#"{{"code":200,"data":{{"media_type":{},"path":"{}","list":[{}]}},"success":true,"message":""}}"#,
+                media_type,
+                media_path,
+                list.join(",")
+            );
+            ("200 OK", "application/json", body.into_bytes())
+        }
+        _ => match state.files.get(&percent_decode(endpoint)) {
+            Some(file) => ("200 OK", "application/octet-stream", file.clone()),
+            None => ("404 Not Found", "text/plain", b"not found".to_vec()),
+        },
+    }
+}
+
+fn query_param<'a>(query: &'a str, key: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .find_map(|pair| pair.split_once('=').filter(|(k, _)| *k == key).map(|(_, v)| v))
+}
+
+fn percent_decode(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|hex| std::str::from_utf8(hex).ok());
+        match (bytes[i], hex.and_then(|hex| u8::from_str_radix(hex, 16).ok())) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}